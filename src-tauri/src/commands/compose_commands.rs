@@ -0,0 +1,53 @@
+use crate::docker::{
+    compose_up, list_compose_projects, remove_compose_project, restart_compose_project,
+    start_compose_project, stop_compose_project,
+};
+use crate::models::compose::{ComposeProject, ComposeUpResult};
+use crate::utils::Result;
+
+#[tauri::command]
+pub async fn get_compose_projects(host: Option<String>) -> Result<Vec<ComposeProject>> {
+    list_compose_projects(host.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn start_compose_project_cmd(project: String, host: Option<String>) -> Result<()> {
+    start_compose_project(host.as_deref(), &project).await
+}
+
+#[tauri::command]
+pub async fn stop_compose_project_cmd(
+    project: String,
+    timeout: Option<i64>,
+    host: Option<String>,
+) -> Result<()> {
+    stop_compose_project(host.as_deref(), &project, timeout).await
+}
+
+#[tauri::command]
+pub async fn restart_compose_project_cmd(
+    project: String,
+    timeout: Option<i64>,
+    host: Option<String>,
+) -> Result<()> {
+    restart_compose_project(host.as_deref(), &project, timeout).await
+}
+
+#[tauri::command]
+pub async fn remove_compose_project_cmd(
+    project: String,
+    force: bool,
+    remove_volumes: bool,
+    host: Option<String>,
+) -> Result<()> {
+    remove_compose_project(host.as_deref(), &project, force, remove_volumes).await
+}
+
+#[tauri::command]
+pub async fn compose_up_cmd(
+    file_path: String,
+    project_name: Option<String>,
+    host: Option<String>,
+) -> Result<ComposeUpResult> {
+    compose_up(host.as_deref(), &file_path, project_name.as_deref()).await
+}
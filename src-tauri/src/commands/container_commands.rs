@@ -1,48 +1,144 @@
 use crate::docker::{
-    exec_container, get_container_logs, get_container_stats, inspect_container, kill_container,
-    list_containers, remove_container, restart_container, start_container, stop_container,
-    ContainerDetails, ContainerListItem, ContainerLogLine, ContainerStatsData,
+    bulk_container_action, create_container, exec_container, export_container_definition,
+    get_container_logs, get_container_stats, inspect_container, kill_container, list_containers,
+    pause_container, recreate_container, remove_container, restart_container, start_container,
+    stop_container, subscribe_container_logs, subscribe_container_stats, unpause_container,
+    unsubscribe_container_logs, unsubscribe_container_stats, update_container_resources,
+    ContainerActionResult, ContainerDefinition, ContainerDetails, ContainerListItem,
+    ContainerLogLine, ContainerPatch, ContainerResources, ContainerSpec, ContainerStatsData,
+    ContainerUpdateResult, RestartPolicySpec,
 };
 use crate::utils::Result;
+use tauri::AppHandle;
 
 #[tauri::command]
-pub async fn get_containers(all: bool) -> Result<Vec<ContainerListItem>> {
-    list_containers(all).await
+pub async fn get_containers(all: bool, host: Option<String>) -> Result<Vec<ContainerListItem>> {
+    list_containers(host.as_deref(), all).await
 }
 
 #[tauri::command]
-pub async fn get_container_details(id: String) -> Result<ContainerDetails> {
-    inspect_container(&id).await
+pub async fn get_container_details(id: String, host: Option<String>) -> Result<ContainerDetails> {
+    inspect_container(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn start_container_cmd(id: String) -> Result<()> {
-    start_container(&id).await
+pub async fn create_container_cmd(
+    spec: ContainerSpec,
+    pull: bool,
+    start: bool,
+    host: Option<String>,
+) -> Result<ContainerDetails> {
+    create_container(host.as_deref(), &spec, pull, start).await
 }
 
 #[tauri::command]
-pub async fn stop_container_cmd(id: String, timeout: Option<i64>) -> Result<()> {
-    stop_container(&id, timeout).await
+pub async fn start_container_cmd(id: String, host: Option<String>) -> Result<()> {
+    start_container(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn restart_container_cmd(id: String, timeout: Option<i64>) -> Result<()> {
-    restart_container(&id, timeout).await
+pub async fn stop_container_cmd(
+    id: String,
+    timeout: Option<i64>,
+    host: Option<String>,
+) -> Result<()> {
+    stop_container(host.as_deref(), &id, timeout).await
+}
+
+#[tauri::command]
+pub async fn restart_container_cmd(
+    id: String,
+    timeout: Option<i64>,
+    host: Option<String>,
+) -> Result<()> {
+    restart_container(host.as_deref(), &id, timeout).await
+}
+
+#[tauri::command]
+pub async fn remove_container_cmd(
+    id: String,
+    force: bool,
+    remove_volumes: bool,
+    host: Option<String>,
+) -> Result<()> {
+    remove_container(host.as_deref(), &id, force, remove_volumes).await
+}
+
+#[tauri::command]
+pub async fn pause_container_cmd(id: String, host: Option<String>) -> Result<()> {
+    pause_container(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn remove_container_cmd(id: String, force: bool, remove_volumes: bool) -> Result<()> {
-    remove_container(&id, force, remove_volumes).await
+pub async fn unpause_container_cmd(id: String, host: Option<String>) -> Result<()> {
+    unpause_container(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn kill_container_cmd(id: String, signal: Option<String>) -> Result<()> {
-    kill_container(&id, signal.as_deref()).await
+pub async fn update_container_resources_cmd(
+    id: String,
+    resources: ContainerResources,
+    restart_policy: Option<RestartPolicySpec>,
+    host: Option<String>,
+) -> Result<ContainerUpdateResult> {
+    update_container_resources(host.as_deref(), &id, &resources, restart_policy.as_ref()).await
+}
+
+#[tauri::command]
+pub async fn recreate_container_cmd(
+    id: String,
+    patch: ContainerPatch,
+    host: Option<String>,
+) -> Result<ContainerDetails> {
+    recreate_container(host.as_deref(), &id, &patch).await
+}
+
+#[tauri::command]
+pub async fn export_container_definition_cmd(
+    id: String,
+    host: Option<String>,
+) -> Result<ContainerDefinition> {
+    export_container_definition(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn get_container_stats_cmd(id: String) -> Result<ContainerStatsData> {
-    get_container_stats(&id).await
+pub async fn bulk_container_action_cmd(
+    ids: Vec<String>,
+    action: String,
+    host: Option<String>,
+) -> Result<Vec<ContainerActionResult>> {
+    bulk_container_action(host.as_deref(), &ids, &action).await
+}
+
+#[tauri::command]
+pub async fn kill_container_cmd(
+    id: String,
+    signal: Option<String>,
+    host: Option<String>,
+) -> Result<()> {
+    kill_container(host.as_deref(), &id, signal.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn get_container_stats_cmd(
+    id: String,
+    host: Option<String>,
+) -> Result<ContainerStatsData> {
+    get_container_stats(host.as_deref(), &id).await
+}
+
+#[tauri::command]
+pub async fn subscribe_container_stats_cmd(
+    app: AppHandle,
+    id: String,
+    host: Option<String>,
+) -> Result<String> {
+    subscribe_container_stats(app, host.as_deref(), &id).await
+}
+
+#[tauri::command]
+pub async fn unsubscribe_container_stats_cmd(subscription_id: String) -> Result<()> {
+    unsubscribe_container_stats(&subscription_id).await
 }
 
 #[tauri::command]
@@ -52,8 +148,33 @@ pub async fn get_container_logs_cmd(
     tail: Option<String>,
     since: Option<String>,
     until: Option<String>,
+    host: Option<String>,
 ) -> Result<Vec<ContainerLogLine>> {
-    get_container_logs(&id, follow.unwrap_or(false), tail, since, until).await
+    get_container_logs(
+        host.as_deref(),
+        &id,
+        follow.unwrap_or(false),
+        tail,
+        since,
+        until,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn subscribe_container_logs_cmd(
+    app: AppHandle,
+    id: String,
+    tail: Option<String>,
+    since: Option<String>,
+    host: Option<String>,
+) -> Result<String> {
+    subscribe_container_logs(app, host.as_deref(), &id, tail, since).await
+}
+
+#[tauri::command]
+pub async fn unsubscribe_container_logs_cmd(subscription_id: String) -> Result<()> {
+    unsubscribe_container_logs(&subscription_id).await
 }
 
 #[tauri::command]
@@ -62,6 +183,14 @@ pub async fn exec_container_cmd(
     cmd: Vec<String>,
     interactive: Option<bool>,
     tty: Option<bool>,
+    host: Option<String>,
 ) -> Result<String> {
-    exec_container(&id, cmd, interactive.unwrap_or(true), tty.unwrap_or(true)).await
+    exec_container(
+        host.as_deref(),
+        &id,
+        cmd,
+        interactive.unwrap_or(true),
+        tty.unwrap_or(true),
+    )
+    .await
 }
@@ -1,24 +1,155 @@
 use crate::docker::{
-    inspect_image, list_images, pull_image, remove_image, ImageDetails, ImageListItem,
+    cancel_image_archive, cancel_image_build, cancel_image_pull, delete_registry_login,
+    image_history, inspect_image, list_images, list_registry_logins, prune_images, pull_image,
+    remove_image, retag_images, save_registry_login, subscribe_image_build, subscribe_image_load,
+    subscribe_image_pull, subscribe_image_push, subscribe_image_save, tag_image, untag_image,
+    ImageBuildRequest, ImageDetails, ImageHistoryEntry, ImageListItem, ImagePruneResponse,
+    ImageRetagResult, RegistryLogin,
 };
 use crate::utils::Result;
+use tauri::AppHandle;
 
 #[tauri::command]
-pub async fn get_images(all: bool) -> Result<Vec<ImageListItem>> {
-    list_images(all).await
+pub async fn get_images(all: bool, host: Option<String>) -> Result<Vec<ImageListItem>> {
+    list_images(host.as_deref(), all).await
 }
 
 #[tauri::command]
-pub async fn get_image_details(id: String) -> Result<ImageDetails> {
-    inspect_image(&id).await
+pub async fn get_image_details(id: String, host: Option<String>) -> Result<ImageDetails> {
+    inspect_image(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn remove_image_cmd(id: String, force: bool, no_prune: bool) -> Result<()> {
-    remove_image(&id, force, no_prune).await
+pub async fn get_image_history(id: String, host: Option<String>) -> Result<Vec<ImageHistoryEntry>> {
+    image_history(host.as_deref(), &id).await
 }
 
 #[tauri::command]
-pub async fn pull_image_cmd(name: String, tag: Option<String>) -> Result<()> {
-    pull_image(&name, tag.as_deref()).await
+pub async fn remove_image_cmd(
+    id: String,
+    force: bool,
+    no_prune: bool,
+    host: Option<String>,
+) -> Result<()> {
+    remove_image(host.as_deref(), &id, force, no_prune).await
+}
+
+#[tauri::command]
+pub async fn tag_image_cmd(id: String, reference: String, host: Option<String>) -> Result<()> {
+    tag_image(host.as_deref(), &id, &reference).await
+}
+
+#[tauri::command]
+pub async fn untag_image_cmd(reference: String, host: Option<String>) -> Result<()> {
+    untag_image(host.as_deref(), &reference).await
+}
+
+#[tauri::command]
+pub async fn retag_images_cmd(
+    sources: Vec<String>,
+    prefix: String,
+    host: Option<String>,
+) -> Result<Vec<ImageRetagResult>> {
+    retag_images(host.as_deref(), &sources, &prefix).await
+}
+
+#[tauri::command]
+pub async fn prune_images_cmd(
+    all: bool,
+    until: Option<String>,
+    labels: Option<Vec<String>>,
+    dry_run: bool,
+    host: Option<String>,
+) -> Result<ImagePruneResponse> {
+    prune_images(
+        host.as_deref(),
+        all,
+        until.as_deref(),
+        &labels.unwrap_or_default(),
+        dry_run,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn pull_image_cmd(name: String, tag: Option<String>, host: Option<String>) -> Result<()> {
+    pull_image(host.as_deref(), &name, tag.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn subscribe_image_pull_cmd(
+    app: AppHandle,
+    name: String,
+    tag: Option<String>,
+    host: Option<String>,
+) -> Result<String> {
+    subscribe_image_pull(app, host.as_deref(), &name, tag.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn cancel_image_pull_cmd(subscription_id: String) -> Result<()> {
+    cancel_image_pull(&subscription_id).await
+}
+
+#[tauri::command]
+pub async fn push_image_cmd(
+    app: AppHandle,
+    name: String,
+    tag: Option<String>,
+    host: Option<String>,
+) -> Result<String> {
+    subscribe_image_push(app, host.as_deref(), &name, tag.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn build_image_cmd(
+    app: AppHandle,
+    request: ImageBuildRequest,
+    host: Option<String>,
+) -> Result<String> {
+    subscribe_image_build(app, host.as_deref(), request).await
+}
+
+#[tauri::command]
+pub async fn cancel_image_build_cmd(subscription_id: String) -> Result<()> {
+    cancel_image_build(&subscription_id).await
+}
+
+#[tauri::command]
+pub async fn save_images_cmd(
+    app: AppHandle,
+    images: Vec<String>,
+    path: String,
+    host: Option<String>,
+) -> Result<String> {
+    subscribe_image_save(app, host.as_deref(), &images, &path).await
+}
+
+#[tauri::command]
+pub async fn load_images_cmd(app: AppHandle, path: String, host: Option<String>) -> Result<String> {
+    subscribe_image_load(app, host.as_deref(), &path).await
+}
+
+#[tauri::command]
+pub async fn cancel_image_archive_cmd(subscription_id: String) -> Result<()> {
+    cancel_image_archive(&subscription_id).await
+}
+
+#[tauri::command]
+pub async fn get_registry_logins() -> Result<Vec<RegistryLogin>> {
+    list_registry_logins().await
+}
+
+#[tauri::command]
+pub async fn add_registry_login(
+    registry: String,
+    username: String,
+    password: String,
+) -> Result<()> {
+    save_registry_login(&registry, &username, &password).await
+}
+
+#[tauri::command]
+pub async fn remove_registry_login(registry: String) -> Result<()> {
+    delete_registry_login(&registry).await
 }
@@ -1,11 +1,15 @@
+pub mod compose_commands;
 pub mod container_commands;
 pub mod image_commands;
 pub mod network_commands;
 pub mod system_commands;
+pub mod terminal_commands;
 pub mod volume_commands;
 
+pub use compose_commands::*;
 pub use container_commands::*;
 pub use image_commands::*;
 pub use network_commands::*;
 pub use system_commands::*;
+pub use terminal_commands::*;
 pub use volume_commands::*;
@@ -1,6 +1,6 @@
 use crate::docker::DOCKER_CLIENT;
 use crate::utils::{DockerError, Result};
-use bollard::network::ListNetworksOptions;
+use bollard::query_parameters::ListNetworksOptions;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::command;
@@ -41,10 +41,10 @@ pub struct NetworkContainer {
 }
 
 #[command]
-pub async fn get_networks() -> Result<Vec<DockerNetwork>> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn get_networks(host: Option<String>) -> Result<Vec<DockerNetwork>> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
-    let options = Some(ListNetworksOptions::<String> {
+    let options = Some(ListNetworksOptions {
         ..Default::default()
     });
 
@@ -108,11 +108,14 @@ pub async fn get_networks() -> Result<Vec<DockerNetwork>> {
 }
 
 #[command]
-pub async fn get_network_details(id: String) -> Result<DockerNetwork> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn get_network_details(id: String, host: Option<String>) -> Result<DockerNetwork> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
     let network = client
-        .inspect_network(&id, None::<bollard::network::InspectNetworkOptions<String>>)
+        .inspect_network(
+            &id,
+            None::<bollard::query_parameters::InspectNetworkOptions>,
+        )
         .await
         .map_err(|e| DockerError::OperationFailed {
             message: format!("Failed to inspect network: {e}"),
@@ -164,8 +167,8 @@ pub async fn get_network_details(id: String) -> Result<DockerNetwork> {
 }
 
 #[command]
-pub async fn remove_network_cmd(id: String) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn remove_network_cmd(id: String, host: Option<String>) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
     client
         .remove_network(&id)
@@ -178,11 +181,11 @@ pub async fn remove_network_cmd(id: String) -> Result<()> {
 }
 
 #[command]
-pub async fn prune_networks_cmd() -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn prune_networks_cmd(host: Option<String>) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
     client
-        .prune_networks(None::<bollard::network::PruneNetworksOptions<String>>)
+        .prune_networks(None::<bollard::query_parameters::PruneNetworksOptions>)
         .await
         .map_err(|e| DockerError::OperationFailed {
             message: format!("Failed to prune networks: {e}"),
@@ -1,16 +1,35 @@
 use crate::docker::{
-    get_connection_status, get_docker_stats, get_docker_system_info, DockerConnectionStatus,
-    DockerStats, DockerSystemInfo, DOCKER_CLIENT,
+    get_connection_status, get_docker_disk_usage, get_docker_stats, get_docker_system_info,
+    start_event_stream, stop_event_stream, DiskUsage, DockerConnectionStatus, DockerStats,
+    DockerSystemInfo, DOCKER_CLIENT,
+};
+use crate::utils::config::{
+    active_profile, delete_profile, list_profiles, save_profile, set_active_profile,
+    ConnectionProfile, ConnectionProfiles,
 };
 use crate::utils::Result;
+use tauri::AppHandle;
 use tracing::{error, info, warn};
 
+/// Connects a host by id (profile name), or the active profile when `host` is omitted
 #[tauri::command]
-pub async fn connect_docker() -> Result<bool> {
+pub async fn connect_docker(app: AppHandle, host: Option<String>) -> Result<bool> {
     info!("Frontend requested Docker connection...");
-    let result = DOCKER_CLIENT.connect().await;
+    let host_id = match host {
+        Some(host_id) => host_id,
+        None => active_profile()?.name,
+    };
+
+    let result = DOCKER_CLIENT.connect(Some(&host_id)).await;
     match &result {
-        Ok(true) => info!("Docker connection command successful"),
+        Ok(true) => {
+            info!("Docker connection command successful");
+            // Push daemon events for the lifetime of this connection
+            if let Err(e) = start_event_stream(app.clone(), Some(&host_id)).await {
+                warn!("Failed to start Docker event stream: {}", e);
+            }
+            DOCKER_CLIENT.start_health_monitor(app, &host_id).await;
+        }
         Ok(false) => warn!("Docker connection command returned false"),
         Err(e) => error!("Docker connection command failed: {}", e),
     }
@@ -18,16 +37,23 @@ pub async fn connect_docker() -> Result<bool> {
 }
 
 #[tauri::command]
-pub async fn disconnect_docker() -> Result<()> {
+pub async fn disconnect_docker(host: Option<String>) -> Result<()> {
     info!("Frontend requested Docker disconnection...");
-    DOCKER_CLIENT.disconnect().await;
+    stop_event_stream(host.as_deref()).await;
+    DOCKER_CLIENT.disconnect(host.as_deref()).await;
     Ok(())
 }
 
 #[tauri::command]
-pub async fn get_docker_connection_status() -> DockerConnectionStatus {
+pub async fn get_connected_hosts() -> Vec<String> {
+    info!("Frontend requested connected hosts...");
+    DOCKER_CLIENT.connected_hosts().await
+}
+
+#[tauri::command]
+pub async fn get_docker_connection_status(host: Option<String>) -> DockerConnectionStatus {
     info!("Frontend requested connection status...");
-    let status = get_connection_status().await;
+    let status = get_connection_status(host.as_deref()).await;
     info!(
         "Connection status: connected={}, error={:?}",
         status.connected, status.error
@@ -36,21 +62,27 @@ pub async fn get_docker_connection_status() -> DockerConnectionStatus {
 }
 
 #[tauri::command]
-pub async fn get_system_info() -> Result<DockerSystemInfo> {
+pub async fn get_system_info(host: Option<String>) -> Result<DockerSystemInfo> {
     info!("Frontend requested system info...");
-    get_docker_system_info().await
+    get_docker_system_info(host.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_system_stats() -> Result<DockerStats> {
+pub async fn get_system_stats(host: Option<String>) -> Result<DockerStats> {
     info!("Frontend requested system stats...");
-    get_docker_stats().await
+    get_docker_stats(host.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn get_disk_usage(host: Option<String>) -> Result<DiskUsage> {
+    info!("Frontend requested disk usage...");
+    get_docker_disk_usage(host.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn test_docker_connection() -> Result<bool> {
+pub async fn test_docker_connection(host: Option<String>) -> Result<bool> {
     info!("Frontend requested connection test...");
-    let result = DOCKER_CLIENT.test_connection().await;
+    let result = DOCKER_CLIENT.test_connection(host.as_deref()).await;
     match &result {
         Ok(true) => info!("Connection test successful"),
         Ok(false) => info!("Connection test failed - not connected"),
@@ -58,3 +90,46 @@ pub async fn test_docker_connection() -> Result<bool> {
     }
     result
 }
+
+#[tauri::command]
+pub async fn get_connection_profiles() -> Result<ConnectionProfiles> {
+    info!("Frontend requested connection profiles...");
+    list_profiles()
+}
+
+#[tauri::command]
+pub async fn add_connection_profile(profile: ConnectionProfile) -> Result<()> {
+    info!(
+        "Frontend requested saving connection profile '{}'",
+        profile.name
+    );
+    save_profile(profile)
+}
+
+#[tauri::command]
+pub async fn remove_connection_profile(name: String) -> Result<()> {
+    info!("Frontend requested removing connection profile '{}'", name);
+    delete_profile(&name)
+}
+
+/// Makes `name` the active profile and the default host, connecting it if needed
+#[tauri::command]
+pub async fn switch_connection_profile(app: AppHandle, name: String) -> Result<bool> {
+    info!(
+        "Frontend requested switching to connection profile '{}'",
+        name
+    );
+    let profile = set_active_profile(&name)?;
+
+    // Other hosts stay connected; the switched-to host becomes the default
+    stop_event_stream(Some(&profile.name)).await;
+    let result = DOCKER_CLIENT.connect_profile(&profile).await;
+    if let Ok(true) = result {
+        DOCKER_CLIENT.set_default_host(&profile.name).await;
+        if let Err(e) = start_event_stream(app.clone(), Some(&profile.name)).await {
+            warn!("Failed to start Docker event stream: {}", e);
+        }
+        DOCKER_CLIENT.start_health_monitor(app, &profile.name).await;
+    }
+    result
+}
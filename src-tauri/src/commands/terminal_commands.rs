@@ -0,0 +1,31 @@
+use crate::terminal::{
+    close_exec_session, open_exec_session, resize_exec_session, write_exec_stdin,
+};
+use crate::utils::Result;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn open_exec_session_cmd(
+    app: AppHandle,
+    id: String,
+    cmd: Vec<String>,
+    tty: Option<bool>,
+    host: Option<String>,
+) -> Result<String> {
+    open_exec_session(app, host.as_deref(), &id, cmd, tty.unwrap_or(true)).await
+}
+
+#[tauri::command]
+pub async fn write_exec_stdin_cmd(session_id: String, data: String) -> Result<()> {
+    write_exec_stdin(&session_id, &data).await
+}
+
+#[tauri::command]
+pub async fn resize_exec_session_cmd(session_id: String, rows: u16, cols: u16) -> Result<()> {
+    resize_exec_session(&session_id, rows, cols).await
+}
+
+#[tauri::command]
+pub async fn close_exec_session_cmd(session_id: String) -> Result<()> {
+    close_exec_session(&session_id).await
+}
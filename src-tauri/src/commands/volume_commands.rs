@@ -1,6 +1,6 @@
 use crate::docker::DOCKER_CLIENT;
 use crate::utils::{DockerError, Result};
-use bollard::volume::{ListVolumesOptions, RemoveVolumeOptions};
+use bollard::query_parameters::{ListVolumesOptions, RemoveVolumeOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::command;
@@ -31,10 +31,10 @@ pub struct VolumePruneResponse {
 }
 
 #[command]
-pub async fn get_volumes() -> Result<Vec<DockerVolume>> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn get_volumes(host: Option<String>) -> Result<Vec<DockerVolume>> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
-    let options = Some(ListVolumesOptions::<String> {
+    let options = Some(ListVolumesOptions {
         ..Default::default()
     });
 
@@ -75,8 +75,8 @@ pub async fn get_volumes() -> Result<Vec<DockerVolume>> {
 }
 
 #[command]
-pub async fn get_volume_details(name: String) -> Result<DockerVolume> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn get_volume_details(name: String, host: Option<String>) -> Result<DockerVolume> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
     let volume = client
         .inspect_volume(&name)
@@ -106,8 +106,12 @@ pub async fn get_volume_details(name: String) -> Result<DockerVolume> {
 }
 
 #[command]
-pub async fn remove_volume_cmd(name: String, force: Option<bool>) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn remove_volume_cmd(
+    name: String,
+    force: Option<bool>,
+    host: Option<String>,
+) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
     let options = RemoveVolumeOptions {
         force: force.unwrap_or(false),
@@ -124,11 +128,11 @@ pub async fn remove_volume_cmd(name: String, force: Option<bool>) -> Result<()>
 }
 
 #[command]
-pub async fn prune_volumes_cmd() -> Result<VolumePruneResponse> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn prune_volumes_cmd(host: Option<String>) -> Result<VolumePruneResponse> {
+    let client = DOCKER_CLIENT.get_host_client(host.as_deref()).await?;
 
     let response = client
-        .prune_volumes(None::<bollard::volume::PruneVolumesOptions<String>>)
+        .prune_volumes(None::<bollard::query_parameters::PruneVolumesOptions>)
         .await
         .map_err(|e| DockerError::OperationFailed {
             message: format!("Failed to prune volumes: {e}"),
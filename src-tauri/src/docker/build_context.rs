@@ -0,0 +1,241 @@
+use crate::utils::{DockerError, Result};
+use futures_util::stream::Stream;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use walkdir::WalkDir;
+
+pub const DEFAULT_DOCKERFILE: &str = "Dockerfile";
+
+const DOCKERIGNORE: &str = ".dockerignore";
+
+/// How much of the packed context is handed to the upload at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Packed chunks waiting for the upload at most
+const CHUNKS_IN_FLIGHT: usize = 4;
+
+/// A build context directory checked for its Dockerfile, with its
+/// `.dockerignore` loaded, ready to be packed
+pub struct BuildContext {
+    root: PathBuf,
+    dockerfile: PathBuf, // relative to the root
+    dockerfile_path: PathBuf,
+    ignore: Gitignore,
+}
+
+impl BuildContext {
+    pub fn open(context_dir: &Path, dockerfile: &str) -> Result<Self> {
+        if !context_dir.is_dir() {
+            return Err(DockerError::InvalidInput {
+                message: format!("Build context {} is not a directory", context_dir.display()),
+            });
+        }
+
+        let dockerfile_path = context_dir.join(dockerfile);
+        if !dockerfile_path.is_file() {
+            return Err(DockerError::InvalidInput {
+                message: format!("Dockerfile {} not found", dockerfile_path.display()),
+            });
+        }
+        let dockerfile_path = dockerfile_path.canonicalize()?;
+        let root = context_dir.canonicalize()?;
+        let dockerfile = dockerfile_path
+            .strip_prefix(&root)
+            .map(Path::to_path_buf)
+            .map_err(|_| DockerError::InvalidInput {
+                message: format!("Dockerfile {dockerfile} is outside the build context"),
+            })?;
+
+        let ignore = load_dockerignore(&root)?;
+        Ok(Self {
+            root,
+            dockerfile,
+            dockerfile_path,
+            ignore,
+        })
+    }
+
+    /// Writes the context as an uncompressed tar archive, leaving out
+    /// everything matched by its `.dockerignore`. The Dockerfile and the
+    /// `.dockerignore` itself are always sent, as the docker CLI does.
+    fn pack(&self, writer: impl Write) -> Result<()> {
+        let always_included = [self.dockerfile.clone(), PathBuf::from(DOCKERIGNORE)];
+
+        let mut archive = tar::Builder::new(writer);
+        archive.follow_symlinks(false);
+
+        let entries = WalkDir::new(&self.root)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|entry| {
+                let relative = entry
+                    .path()
+                    .strip_prefix(&self.root)
+                    .unwrap_or(entry.path());
+                always_included.iter().any(|path| path == relative)
+                    || !self
+                        .ignore
+                        .matched(relative, entry.file_type().is_dir())
+                        .is_ignore()
+            });
+
+        let mut dockerfile_packed = false;
+        for entry in entries {
+            let entry = entry.map_err(|e| DockerError::Io(e.into()))?;
+            let relative = entry
+                .path()
+                .strip_prefix(&self.root)
+                .unwrap_or(entry.path());
+            dockerfile_packed |= relative == always_included[0];
+            if entry.file_type().is_dir() {
+                archive.append_dir(relative, entry.path())?;
+            } else {
+                archive.append_path_with_name(entry.path(), relative)?;
+            }
+        }
+
+        // An ignored parent directory hides the Dockerfile from the walk
+        if !dockerfile_packed {
+            archive.append_path_with_name(&self.dockerfile_path, &always_included[0])?;
+        }
+
+        archive.into_inner()?.flush()?;
+        Ok(())
+    }
+
+    /// Packs the context on a blocking thread while the returned stream hands
+    /// the chunks to the upload, so only a few chunks are in memory at once.
+    /// If packing fails, the message goes into `pack_error` and the stream
+    /// ends with an error instead of a truncated archive.
+    pub fn into_stream(
+        self,
+        pack_error: Arc<Mutex<Option<String>>>,
+    ) -> impl Stream<Item = std::io::Result<Vec<u8>>> {
+        let (sender, receiver) = mpsc::channel(CHUNKS_IN_FLIGHT);
+        tauri::async_runtime::spawn_blocking(move || {
+            match self.pack(ChunkWriter::new(sender.clone())) {
+                Ok(()) => {}
+                // The upload ended early, nobody wants the rest
+                Err(DockerError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+                Err(e) => {
+                    *pack_error.lock().unwrap() = Some(e.to_string());
+                    let _ = sender.blocking_send(Err(std::io::Error::other(e.to_string())));
+                }
+            }
+        });
+        futures_util::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        })
+    }
+}
+
+/// Hands what is written to it to an async receiver in chunks. Writing fails
+/// with `BrokenPipe` once the receiver is gone.
+struct ChunkWriter {
+    sender: mpsc::Sender<std::io::Result<Vec<u8>>>,
+    buffer: Vec<u8>,
+}
+
+impl ChunkWriter {
+    fn new(sender: mpsc::Sender<std::io::Result<Vec<u8>>>) -> Self {
+        Self {
+            sender,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        }
+    }
+
+    fn send(&mut self) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+        self.sender
+            .blocking_send(Ok(chunk))
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.send()
+    }
+}
+
+/// Reads `.dockerignore` into a matcher. Patterns are relative to the context
+/// root, so each one is anchored there unlike in a `.gitignore`.
+fn load_dockerignore(context_root: &Path) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(context_root);
+
+    let path = context_root.join(DOCKERIGNORE);
+    if path.is_file() {
+        for line in std::fs::read_to_string(&path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negation, pattern) = match line.strip_prefix('!') {
+                Some(pattern) => ("!", pattern.trim()),
+                None => ("", line),
+            };
+            let pattern = pattern.trim_start_matches("./").trim_start_matches('/');
+            builder
+                .add_line(None, &format!("{negation}/{pattern}"))
+                .map_err(|e| DockerError::InvalidInput {
+                    message: format!("Invalid .dockerignore pattern '{line}': {e}"),
+                })?;
+        }
+    }
+
+    builder.build().map_err(|e| DockerError::InvalidInput {
+        message: format!("Invalid .dockerignore: {e}"),
+    })
+}
+
+/// Base images named by the `FROM` instructions of a Dockerfile, skipping
+/// references to earlier build stages and `scratch`.
+pub fn base_images(dockerfile: &str) -> Vec<String> {
+    let mut stages: Vec<String> = Vec::new();
+    let mut images: Vec<String> = Vec::new();
+
+    for line in dockerfile.lines() {
+        let mut words = line.split_whitespace();
+        if !words
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case("FROM"))
+        {
+            continue;
+        }
+        let mut words = words.filter(|word| !word.starts_with("--"));
+        let Some(image) = words.next() else {
+            continue;
+        };
+        if image != "scratch"
+            && !image.contains('$')
+            && !stages.iter().any(|stage| stage.eq_ignore_ascii_case(image))
+            && !images.iter().any(|known| known == image)
+        {
+            images.push(image.to_string());
+        }
+        if words
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case("AS"))
+        {
+            if let Some(stage) = words.next() {
+                stages.push(stage.to_string());
+            }
+        }
+    }
+
+    images
+}
@@ -1,26 +1,76 @@
+use crate::docker::events::start_event_stream;
+use crate::docker::ssh::{SshTunnelSpec, DEFAULT_REMOTE_SOCKET, SSH_TUNNELS};
+use crate::utils::config::{
+    active_profile, get_profile, parse_api_version, ConnectionEndpoint, ConnectionProfile,
+};
 use crate::utils::{log_docker_connection, DockerError, Result};
-use bollard::Docker;
+use bollard::{ClientVersion, Docker, API_DEFAULT_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{watch, Mutex};
 use tracing::{error, info, warn};
 
+pub const DOCKER_CONNECTION_EVENT: &str = "docker-connection";
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Registry of live Docker connections keyed by host id (the name of the
+/// connection profile they were opened with). Commands that do not name a
+/// host use the default one, which is the first host connected or the one
+/// last switched to.
 pub struct DockerClient {
-    client: Arc<Mutex<Option<Docker>>>,
+    clients: Arc<Mutex<HashMap<String, Docker>>>,
+    default_host: Mutex<Option<String>>,
+    monitors: Mutex<HashMap<String, HealthMonitor>>,
+}
+
+/// Background ping loop of one host. `generation` is bumped every time the
+/// monitor restores the connection, which is what resuming streams wait on.
+struct HealthMonitor {
+    generation: watch::Sender<u64>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionHealthEvent {
+    pub host: String,
+    pub state: String, // "connected", "disconnected" or "reconnecting"
+    pub attempt: u32,
+    pub retry_in_ms: Option<u64>,
+    pub error: Option<String>,
 }
 
 impl DockerClient {
     pub fn new() -> Self {
         Self {
-            client: Arc::new(Mutex::new(None)),
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            default_host: Mutex::new(None),
+            monitors: Mutex::new(HashMap::new()),
         }
     }
 
-    pub async fn connect(&self) -> Result<bool> {
-        info!("Attempting to connect to Docker daemon...");
-        let mut client_guard = self.client.lock().await;
+    /// Connects a host by profile name, or the active profile when `host` is `None`
+    pub async fn connect(&self, host: Option<&str>) -> Result<bool> {
+        let profile = match host {
+            Some(name) => get_profile(name)?,
+            None => active_profile()?,
+        };
+        self.connect_profile(&profile).await
+    }
+
+    pub async fn connect_profile(&self, profile: &ConnectionProfile) -> Result<bool> {
+        info!(
+            "Attempting to connect to Docker daemon using profile '{}'...",
+            profile.name
+        );
 
-        // Try different connection methods with detailed logging
-        let docker_result = self.try_connect_methods().await;
+        let docker_result = create_client(profile).await;
 
         match docker_result {
             Ok(docker) => {
@@ -32,14 +82,22 @@ impl DockerClient {
                             "Docker connection successful! Version: {:?}",
                             version.version
                         );
-                        *client_guard = Some(docker);
+                        self.clients
+                            .lock()
+                            .await
+                            .insert(profile.name.clone(), docker);
+                        self.default_host
+                            .lock()
+                            .await
+                            .get_or_insert_with(|| profile.name.clone());
                         log_docker_connection(true, None);
                         Ok(true)
                     }
                     Err(e) => {
                         error!("Docker version check failed: {}", e);
                         log_docker_connection(false, Some(&e.to_string()));
-                        Err(DockerError::Connection(e))
+                        SSH_TUNNELS.close(&profile.name).await;
+                        Err(certificate_error(e))
                     }
                 }
             }
@@ -51,116 +109,60 @@ impl DockerClient {
         }
     }
 
-    async fn try_connect_methods(&self) -> Result<Docker> {
-        info!("Trying various Docker connection methods...");
+    /// Disconnects a host (the default one when `host` is `None`)
+    pub async fn disconnect(&self, host: Option<&str>) {
+        let Some(host_id) = self.host_id(host).await else {
+            return;
+        };
 
-        // Method 1: Unix socket (most common on Linux/macOS)
-        #[cfg(unix)]
-        {
-            info!("Trying Unix socket connection...");
-            match Docker::connect_with_socket_defaults() {
-                Ok(docker) => {
-                    info!("Unix socket connection successful");
-                    return Ok(docker);
-                }
-                Err(e) => {
-                    warn!("Unix socket connection failed: {}", e);
-                }
-            }
+        info!("Disconnecting from Docker host '{}'...", host_id);
+        if let Some(monitor) = self.monitors.lock().await.remove(&host_id) {
+            // Dropping the sender also releases streams waiting for a reconnect
+            monitor.task.abort();
         }
+        self.clients.lock().await.remove(&host_id);
+        SSH_TUNNELS.close(&host_id).await;
 
-        // Method 2: Local defaults (tries multiple methods)
-        info!("Trying local defaults connection...");
-        match Docker::connect_with_local_defaults() {
-            Ok(docker) => {
-                info!("Local defaults connection successful");
-                return Ok(docker);
-            }
-            Err(e) => {
-                warn!("Local defaults connection failed: {}", e);
-            }
+        let mut default_host = self.default_host.lock().await;
+        if default_host.as_deref() == Some(host_id.as_str()) {
+            *default_host = None;
         }
+        info!("Docker client disconnected");
+    }
 
-        // Method 3: HTTP connection
-        info!("Trying HTTP connection...");
-        match Docker::connect_with_http_defaults() {
-            Ok(docker) => {
-                info!("HTTP connection successful");
-                return Ok(docker);
-            }
-            Err(e) => {
-                warn!("HTTP connection failed: {}", e);
-            }
-        }
+    pub async fn set_default_host(&self, host_id: &str) {
+        *self.default_host.lock().await = Some(host_id.to_string());
+    }
 
-        // Method 4: Try explicit socket path
-        #[cfg(unix)]
-        {
-            let socket_paths = [
-                "/var/run/docker.sock",
-                "/run/docker.sock",
-                "/run/user/1000/docker.sock", // rootless Docker
-            ];
-
-            for socket_path in &socket_paths {
-                info!("Trying explicit socket path: {}", socket_path);
-                match Docker::connect_with_socket(socket_path, 120, bollard::API_DEFAULT_VERSION) {
-                    Ok(docker) => {
-                        info!("Explicit socket connection successful: {}", socket_path);
-                        return Ok(docker);
-                    }
-                    Err(e) => {
-                        warn!(
-                            "Explicit socket connection failed for {}: {}",
-                            socket_path, e
-                        );
-                    }
-                }
-            }
+    /// Resolves an optional host id to the id a command will actually use
+    pub async fn host_id(&self, host: Option<&str>) -> Option<String> {
+        match host {
+            Some(host_id) => Some(host_id.to_string()),
+            None => self.default_host.lock().await.clone(),
         }
+    }
 
-        // Method 5: Try TCP connections (for Docker Machine, remote Docker, etc.)
-        let tcp_addresses = [
-            "tcp://localhost:2375",
-            "tcp://localhost:2376",
-            "tcp://127.0.0.1:2375",
-            "tcp://127.0.0.1:2376",
-        ];
-
-        for tcp_addr in &tcp_addresses {
-            info!("Trying TCP connection: {}", tcp_addr);
-            match Docker::connect_with_http(tcp_addr, 120, bollard::API_DEFAULT_VERSION) {
-                Ok(docker) => {
-                    info!("TCP connection successful: {}", tcp_addr);
-                    return Ok(docker);
-                }
-                Err(e) => {
-                    warn!("TCP connection failed for {}: {}", tcp_addr, e);
-                }
-            }
+    async fn is_monitored(&self, host: Option<&str>) -> bool {
+        match self.host_id(host).await {
+            Some(host_id) => self.monitors.lock().await.contains_key(&host_id),
+            None => false,
         }
-
-        error!("All Docker connection methods failed");
-        Err(DockerError::DaemonNotAvailable)
     }
 
-    pub async fn disconnect(&self) {
-        info!("Disconnecting from Docker daemon...");
-        let mut client_guard = self.client.lock().await;
-        *client_guard = None;
-        info!("Docker client disconnected");
+    pub async fn connected_hosts(&self) -> Vec<String> {
+        let mut hosts: Vec<String> = self.clients.lock().await.keys().cloned().collect();
+        hosts.sort();
+        hosts
     }
 
-    #[allow(dead_code)]
-    pub async fn is_connected(&self) -> bool {
-        let client_guard = self.client.lock().await;
-        client_guard.is_some()
-    }
+    pub async fn get_host_client(&self, host: Option<&str>) -> Result<Docker> {
+        let client = match self.host_id(host).await {
+            Some(host_id) => self.clients.lock().await.get(&host_id).cloned(),
+            None => None,
+        };
 
-    pub async fn get_client(&self) -> Result<Docker> {
-        let client_guard = self.client.lock().await;
-        match client_guard.as_ref() {
-            Some(client) => Ok(client.clone()),
+        match client {
+            Some(client) => Ok(client),
             None => {
                 warn!("Docker client not connected");
                 Err(DockerError::DaemonNotAvailable)
@@ -168,8 +170,8 @@ impl DockerClient {
         }
     }
 
-    pub async fn test_connection(&self) -> Result<bool> {
-        match self.get_client().await {
+    pub async fn test_connection(&self, host: Option<&str>) -> Result<bool> {
+        match self.get_host_client(host).await {
             Ok(client) => {
                 info!("Testing Docker connection...");
                 match client.version().await {
@@ -182,8 +184,10 @@ impl DockerClient {
                     }
                     Err(e) => {
                         warn!("Docker connection test failed: {}", e);
-                        // Connection lost, clear it
-                        self.disconnect().await;
+                        // Connection lost, clear it, unless the health monitor is restoring it
+                        if !self.is_monitored(host).await {
+                            self.disconnect(host).await;
+                        }
                         Err(DockerError::Connection(e))
                     }
                 }
@@ -196,12 +200,248 @@ impl DockerClient {
     }
 }
 
+impl DockerClient {
+    /// Starts pinging a connected host every few seconds. When a ping fails
+    /// the monitor emits `docker-connection` events (`disconnected`, then
+    /// `reconnecting` with exponential backoff) and rebuilds the client from
+    /// the host's profile, emitting `connected` once the daemon answers again.
+    pub async fn start_health_monitor(&'static self, app: AppHandle, host_id: &str) {
+        let mut monitors = self.monitors.lock().await;
+        if let Some(previous) = monitors.remove(host_id) {
+            previous.task.abort();
+        }
+
+        let (generation, _) = watch::channel(0);
+        let task_generation = generation.clone();
+        let task_host = host_id.to_string();
+        let task = tauri::async_runtime::spawn(async move {
+            self.monitor_host(app, task_host, task_generation).await
+        });
+
+        monitors.insert(host_id.to_string(), HealthMonitor { generation, task });
+        info!("Health monitor started for host '{}'", host_id);
+    }
+
+    async fn monitor_host(&self, app: AppHandle, host_id: String, generation: watch::Sender<u64>) {
+        let emit =
+            |state: &str, attempt: u32, retry_in: Option<Duration>, error: Option<String>| {
+                let event = ConnectionHealthEvent {
+                    host: host_id.clone(),
+                    state: state.to_string(),
+                    attempt,
+                    retry_in_ms: retry_in.map(|d| d.as_millis() as u64),
+                    error,
+                };
+                if let Err(e) = app.emit(DOCKER_CONNECTION_EVENT, event) {
+                    warn!("Failed to emit connection health event: {}", e);
+                }
+            };
+
+        loop {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+            let Ok(client) = self.get_host_client(Some(&host_id)).await else {
+                return;
+            };
+            let error = match client.ping().await {
+                Ok(_) => continue,
+                Err(e) => e.to_string(),
+            };
+
+            warn!("Lost connection to host '{}': {}", host_id, error);
+            emit("disconnected", 0, None, Some(error));
+
+            let mut delay = RECONNECT_INITIAL_DELAY;
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                emit("reconnecting", attempt, Some(delay), None);
+                tokio::time::sleep(delay).await;
+
+                match self.restore_client(&host_id, &client).await {
+                    Ok(()) => break,
+                    Err(e) => {
+                        warn!("Reconnecting host '{}' failed: {}", host_id, e);
+                        emit("disconnected", attempt, None, Some(e.to_string()));
+                        delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                    }
+                }
+            }
+
+            info!("Connection to host '{}' restored", host_id);
+            generation.send_modify(|g| *g += 1);
+            emit("connected", attempt, None, None);
+            if let Err(e) = start_event_stream(app.clone(), Some(&host_id)).await {
+                warn!("Failed to restart Docker event stream: {}", e);
+            }
+        }
+    }
+
+    /// Reuses the existing client if the daemon is reachable through it again
+    /// (e.g. after an SSH tunnel came back), otherwise builds a fresh one.
+    async fn restore_client(&self, host_id: &str, current: &Docker) -> Result<()> {
+        if current.ping().await.is_ok() {
+            return Ok(());
+        }
+
+        let tunnel_reconnecting = SSH_TUNNELS
+            .status(host_id)
+            .await
+            .is_some_and(|status| status.state == "reconnecting");
+        if tunnel_reconnecting {
+            return Err(DockerError::SshTunnel {
+                message: "Waiting for the SSH tunnel to come back".to_string(),
+            });
+        }
+
+        let profile = get_profile(host_id)?;
+        let docker = create_client(&profile).await?;
+        docker.version().await.map_err(certificate_error)?;
+        self.clients
+            .lock()
+            .await
+            .insert(host_id.to_string(), docker);
+        Ok(())
+    }
+
+    /// Called by a streaming task whose stream failed. If the daemon is still
+    /// reachable the failure was not a connection loss and `None` is returned;
+    /// otherwise this waits for the health monitor to restore the host and
+    /// returns the new client, or `None` if the host gets disconnected.
+    pub async fn wait_for_reconnect(&self, host_id: &str, current: &Docker) -> Option<Docker> {
+        // Read the generation first so a restore during the ping is not missed
+        let mut generation = {
+            let monitors = self.monitors.lock().await;
+            monitors.get(host_id)?.generation.subscribe()
+        };
+        let seen = *generation.borrow_and_update();
+
+        if current.ping().await.is_ok() {
+            return None;
+        }
+
+        // The monitor may already have replaced the client before we subscribed
+        if let Ok(client) = self.get_host_client(Some(host_id)).await {
+            if client.ping().await.is_ok() {
+                return Some(client);
+            }
+        }
+
+        generation.wait_for(|g| *g > seen).await.ok()?;
+
+        self.get_host_client(Some(host_id)).await.ok()
+    }
+}
+
 impl Default for DockerClient {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Builds a bollard client for a profile's endpoint, opening an SSH tunnel first if needed
+async fn create_client(profile: &ConnectionProfile) -> Result<Docker> {
+    let api_version = match &profile.api_version {
+        Some(version) => {
+            let (major_version, minor_version) = parse_api_version(version)?;
+            ClientVersion {
+                major_version,
+                minor_version,
+            }
+        }
+        None => *API_DEFAULT_VERSION,
+    };
+
+    let docker = match &profile.endpoint {
+        ConnectionEndpoint::Unix { socket_path } => {
+            info!("Connecting through socket {}", socket_path);
+            Docker::connect_with_socket(socket_path, profile.timeout, &api_version)?
+        }
+        ConnectionEndpoint::Tcp { url } => match profile.tls_paths() {
+            Some(tls) => {
+                info!("Connecting over TLS to {}", url);
+                for path in [&tls.key, &tls.cert, &tls.ca] {
+                    if !path.is_file() {
+                        return Err(DockerError::Certificate {
+                            message: format!("{} does not exist", path.display()),
+                        });
+                    }
+                }
+                Docker::connect_with_ssl(
+                    url,
+                    &tls.key,
+                    &tls.cert,
+                    &tls.ca,
+                    profile.timeout,
+                    &api_version,
+                )
+                .map_err(certificate_error)?
+            }
+            None => {
+                info!("Connecting over TCP to {}", url);
+                Docker::connect_with_http(url, profile.timeout, &api_version)?
+            }
+        },
+        ConnectionEndpoint::Ssh {
+            destination,
+            remote_socket,
+            identity_file,
+        } => {
+            info!("Connecting over SSH to {}", destination);
+            let spec = SshTunnelSpec {
+                destination: destination.clone(),
+                remote_socket: remote_socket
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_REMOTE_SOCKET.to_string()),
+                identity_file: identity_file.clone(),
+            };
+            let local_port = SSH_TUNNELS.open(&profile.name, spec).await?;
+            Docker::connect_with_http(
+                &format!("tcp://127.0.0.1:{local_port}"),
+                profile.timeout,
+                &api_version,
+            )?
+        }
+    };
+
+    // Without a pinned version, settle on the lower of ours and the daemon's
+    if profile.api_version.is_some() {
+        return Ok(docker);
+    }
+    docker.negotiate_version().await.map_err(certificate_error)
+}
+
+/// Maps certificate loading and TLS handshake failures to `DockerError::Certificate`
+fn certificate_error(e: bollard::errors::Error) -> DockerError {
+    use bollard::errors::Error;
+
+    match &e {
+        Error::CertPathError { .. }
+        | Error::CertMultipleKeys { .. }
+        | Error::CertParseError { .. }
+        | Error::NoNativeCertsError { .. } => DockerError::Certificate {
+            message: e.to_string(),
+        },
+        _ if is_tls_failure(&e) => DockerError::Certificate {
+            message: e.to_string(),
+        },
+        _ => DockerError::Connection(e),
+    }
+}
+
+// Handshake errors surface as hyper errors; look through the source chain
+fn is_tls_failure(e: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(e);
+    while let Some(err) = current {
+        let message = err.to_string().to_lowercase();
+        if message.contains("certificate") || message.contains("handshake") {
+            return true;
+        }
+        current = err.source();
+    }
+    false
+}
+
 // Global Docker client instance
 lazy_static::lazy_static! {
     pub static ref DOCKER_CLIENT: DockerClient = DockerClient::new();
@@ -1,7 +1,1411 @@
-// TODO: Compose operations
-use crate::utils::error::DockerResult;
+use crate::docker::client::DOCKER_CLIENT;
+use crate::docker::containers::{
+    list_containers, remove_container, restart_container, start_container, stop_container,
+    ContainerListItem,
+};
+use crate::docker::images::pull_image;
+use crate::docker::registry::normalize_image_reference;
+use crate::models::compose::{
+    ComposeCommand, ComposeContainer, ComposeFile, ComposeNetworkSpec, ComposePort, ComposeProject,
+    ComposeService, ComposeServiceSpec, ComposeServiceVolume, ComposeUpResult, ComposeVolumeSpec,
+};
+use crate::utils::{log_docker_operation, DockerError, Result};
+use bollard::models::{
+    ContainerCreateBody, EndpointSettings, HostConfig, NetworkConnectRequest, NetworkCreateRequest,
+    NetworkingConfig, PortBinding, PortMap, RestartPolicy, RestartPolicyNameEnum,
+    VolumeCreateOptions,
+};
+use bollard::query_parameters::{
+    CreateContainerOptions, InspectContainerOptions, InspectNetworkOptions,
+};
+use bollard::Docker;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
-pub async fn list_compose_stacks() -> DockerResult<()> {
-    // Compose operations implementation
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+pub const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+pub const COMPOSE_CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+pub const COMPOSE_WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+pub const COMPOSE_CONTAINER_NUMBER_LABEL: &str = "com.docker.compose.container-number";
+pub const COMPOSE_ONEOFF_LABEL: &str = "com.docker.compose.oneoff";
+pub const COMPOSE_NETWORK_LABEL: &str = "com.docker.compose.network";
+pub const COMPOSE_VOLUME_LABEL: &str = "com.docker.compose.volume";
+
+pub async fn list_compose_projects(host: Option<&str>) -> Result<Vec<ComposeProject>> {
+    let containers = list_containers(host, true).await?;
+
+    let mut grouped: BTreeMap<String, Vec<ContainerListItem>> = BTreeMap::new();
+    for container in containers {
+        if let Some(project) = container.labels.get(COMPOSE_PROJECT_LABEL) {
+            grouped.entry(project.clone()).or_default().push(container);
+        }
+    }
+
+    let projects: Vec<ComposeProject> = grouped
+        .into_iter()
+        .map(|(name, containers)| build_compose_project(name, containers))
+        .collect();
+
+    log_docker_operation(
+        "list_compose_projects",
+        true,
+        Some(&format!("Found {} compose projects", projects.len())),
+    );
+    Ok(projects)
+}
+
+pub async fn start_compose_project(host: Option<&str>, project: &str) -> Result<()> {
+    let containers = project_containers(host, project).await?;
+    let mut failures = Vec::new();
+
+    for container in &containers {
+        if container.state != "running" {
+            if let Err(e) = start_container(host, &container.id).await {
+                failures.push(format!("{}: {e}", container_name(container)));
+            }
+        }
+    }
+
+    finish_project_operation("start_compose_project", project, failures)
+}
+
+pub async fn stop_compose_project(
+    host: Option<&str>,
+    project: &str,
+    timeout: Option<i64>,
+) -> Result<()> {
+    let containers = project_containers(host, project).await?;
+    let mut failures = Vec::new();
+
+    for container in &containers {
+        if container.state == "running" || container.state == "paused" {
+            if let Err(e) = stop_container(host, &container.id, timeout).await {
+                failures.push(format!("{}: {e}", container_name(container)));
+            }
+        }
+    }
+
+    finish_project_operation("stop_compose_project", project, failures)
+}
+
+pub async fn restart_compose_project(
+    host: Option<&str>,
+    project: &str,
+    timeout: Option<i64>,
+) -> Result<()> {
+    let containers = project_containers(host, project).await?;
+    let mut failures = Vec::new();
+
+    for container in &containers {
+        if let Err(e) = restart_container(host, &container.id, timeout).await {
+            failures.push(format!("{}: {e}", container_name(container)));
+        }
+    }
+
+    finish_project_operation("restart_compose_project", project, failures)
+}
+
+pub async fn remove_compose_project(
+    host: Option<&str>,
+    project: &str,
+    force: bool,
+    remove_volumes: bool,
+) -> Result<()> {
+    let containers = project_containers(host, project).await?;
+    let mut failures = Vec::new();
+
+    for container in &containers {
+        if let Err(e) = remove_container(host, &container.id, force, remove_volumes).await {
+            failures.push(format!("{}: {e}", container_name(container)));
+        }
+    }
+
+    finish_project_operation("remove_compose_project", project, failures)
+}
+
+async fn project_containers(host: Option<&str>, project: &str) -> Result<Vec<ContainerListItem>> {
+    let containers: Vec<ContainerListItem> = list_containers(host, true)
+        .await?
+        .into_iter()
+        .filter(|c| c.labels.get(COMPOSE_PROJECT_LABEL).map(String::as_str) == Some(project))
+        .collect();
+
+    if containers.is_empty() {
+        return Err(DockerError::InvalidInput {
+            message: format!("Compose project '{project}' has no containers"),
+        });
+    }
+    Ok(containers)
+}
+
+fn finish_project_operation(operation: &str, project: &str, failures: Vec<String>) -> Result<()> {
+    if failures.is_empty() {
+        log_docker_operation(operation, true, Some(&format!("Compose project {project}")));
+        Ok(())
+    } else {
+        let message = failures.join("; ");
+        log_docker_operation(operation, false, Some(&message));
+        Err(DockerError::OperationFailed {
+            message: format!("Compose project {project}: {message}"),
+        })
+    }
+}
+
+fn container_name(container: &ContainerListItem) -> String {
+    container
+        .names
+        .first()
+        .map(|n| n.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| container.id.clone())
+}
+
+fn build_compose_project(name: String, containers: Vec<ContainerListItem>) -> ComposeProject {
+    let label = |key: &str| {
+        containers
+            .iter()
+            .find_map(|c| c.labels.get(key))
+            .filter(|v| !v.is_empty())
+            .cloned()
+    };
+
+    let working_dir = label(COMPOSE_WORKING_DIR_LABEL);
+    let config_files = label(COMPOSE_CONFIG_FILES_LABEL)
+        .map(|files| files.split(',').map(|f| f.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    let containers_total = containers.len();
+    let containers_running = containers.iter().filter(|c| c.state == "running").count();
+    let status = if containers_running == 0 {
+        "stopped"
+    } else if containers_running == containers_total {
+        "running"
+    } else {
+        "partial"
+    };
+
+    let mut services: BTreeMap<String, Vec<ComposeContainer>> = BTreeMap::new();
+    for container in containers {
+        let service = container
+            .labels
+            .get(COMPOSE_SERVICE_LABEL)
+            .cloned()
+            .unwrap_or_default();
+        services.entry(service).or_default().push(ComposeContainer {
+            name: container_name(&container),
+            id: container.id,
+            image: container.image,
+            state: container.state,
+            status: container.status,
+        });
+    }
+
+    ComposeProject {
+        name,
+        working_dir,
+        config_files,
+        services: services
+            .into_iter()
+            .map(|(name, containers)| ComposeService { name, containers })
+            .collect(),
+        containers_total,
+        containers_running,
+        status: status.to_string(),
+    }
+}
+
+/// Brings up the stack described by a compose file: creates its networks and
+/// volumes, then creates and starts each service's container in `depends_on`
+/// order. Resources that already exist are reused, so `up` can be re-run.
+pub async fn compose_up(
+    host: Option<&str>,
+    file_path: &str,
+    project_name: Option<&str>,
+) -> Result<ComposeUpResult> {
+    let path = std::fs::canonicalize(file_path)?;
+    let contents = std::fs::read_to_string(&path)?;
+    let working_dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    // Variables come from the host environment first, then the project's `.env`
+    let dotenv = match std::fs::read_to_string(working_dir.join(".env")) {
+        Ok(contents) => parse_env_file(&contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let lookup = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .or_else(|| dotenv.get(name).cloned())
+    };
+    let compose = parse_compose_file(&contents, &lookup).map_err(|e| match e {
+        DockerError::InvalidInput { message } => DockerError::InvalidInput {
+            message: format!("Invalid compose file {file_path}: {message}"),
+        },
+        e => e,
+    })?;
+    let project = project_name
+        .map(str::to_string)
+        .or_else(|| compose.name.clone())
+        .or_else(|| {
+            working_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+        })
+        .map(|name| normalize_project_name(&name))
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| DockerError::InvalidInput {
+            message: "Could not determine a compose project name".to_string(),
+        })?;
+
+    let order = service_start_order(&compose)?;
+    let network_keys = service_network_keys(&compose)?;
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+    let mut result = ComposeUpResult {
+        project: project.clone(),
+        networks_created: Vec::new(),
+        volumes_created: Vec::new(),
+        containers_started: Vec::new(),
+    };
+
+    for key in &network_keys {
+        let spec = compose
+            .networks
+            .get(key)
+            .cloned()
+            .flatten()
+            .unwrap_or_default();
+        let name = network_name(&project, key, &compose);
+        if client
+            .inspect_network(&name, None::<InspectNetworkOptions>)
+            .await
+            .is_ok()
+        {
+            continue;
+        }
+        if spec.external {
+            return Err(DockerError::NetworkNotFound { id: name });
+        }
+
+        let mut labels: HashMap<String, String> = spec.labels.to_map().into_iter().collect();
+        labels.insert(COMPOSE_PROJECT_LABEL.to_string(), project.clone());
+        labels.insert(COMPOSE_NETWORK_LABEL.to_string(), key.clone());
+
+        client
+            .create_network(NetworkCreateRequest {
+                name: name.clone(),
+                driver: Some(spec.driver.unwrap_or_else(|| "bridge".to_string())),
+                options: Some(spec.driver_opts.into_iter().collect()),
+                labels: Some(labels),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| compose_error(&project, format!("create network {name}"), e))?;
+        result.networks_created.push(name);
+    }
+
+    // Named volumes declared at the top level
+    for (key, spec) in &compose.volumes {
+        let spec = spec.clone().unwrap_or_default();
+        let name = volume_name(&project, key, &compose);
+        if client.inspect_volume(&name).await.is_ok() {
+            continue;
+        }
+        if spec.external {
+            return Err(DockerError::VolumeNotFound { id: name });
+        }
+
+        let mut labels: HashMap<String, String> = spec.labels.to_map().into_iter().collect();
+        labels.insert(COMPOSE_PROJECT_LABEL.to_string(), project.clone());
+        labels.insert(COMPOSE_VOLUME_LABEL.to_string(), key.clone());
+
+        client
+            .create_volume(VolumeCreateOptions {
+                name: Some(name.clone()),
+                driver: Some(spec.driver.unwrap_or_else(|| "local".to_string())),
+                driver_opts: Some(spec.driver_opts.into_iter().collect()),
+                labels: Some(labels),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| compose_error(&project, format!("create volume {name}"), e))?;
+        result.volumes_created.push(name);
+    }
+
+    for service_name in order {
+        let service = &compose.services[&service_name];
+        let container_name = service
+            .container_name
+            .clone()
+            .unwrap_or_else(|| format!("{project}-{service_name}-1"));
+
+        let existing = client
+            .inspect_container(&container_name, None::<InspectContainerOptions>)
+            .await
+            .ok();
+        let container_id = match existing {
+            Some(container) => {
+                let running = container
+                    .state
+                    .as_ref()
+                    .and_then(|s| s.running)
+                    .unwrap_or_default();
+                if running {
+                    continue;
+                }
+                container.id.unwrap_or(container_name.clone())
+            }
+            None => {
+                create_service_container(
+                    host,
+                    &client,
+                    &project,
+                    &service_name,
+                    service,
+                    &container_name,
+                    &compose,
+                    &path,
+                    &working_dir,
+                )
+                .await?
+            }
+        };
+
+        start_container(host, &container_id).await?;
+        result.containers_started.push(container_name);
+    }
+
+    log_docker_operation(
+        "compose_up",
+        true,
+        Some(&format!(
+            "Project {project}: {} networks, {} volumes created, {} containers started",
+            result.networks_created.len(),
+            result.volumes_created.len(),
+            result.containers_started.len()
+        )),
+    );
+    Ok(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn create_service_container(
+    host: Option<&str>,
+    client: &Docker,
+    project: &str,
+    service_name: &str,
+    service: &ComposeServiceSpec,
+    container_name: &str,
+    compose: &ComposeFile,
+    config_file: &Path,
+    working_dir: &Path,
+) -> Result<String> {
+    let image = service
+        .image
+        .as_deref()
+        .map(normalize_image_reference)
+        .ok_or_else(|| DockerError::InvalidInput {
+            message: format!("Service '{service_name}' has no image (build is not supported)"),
+        })?;
+
+    if let Err(e) = client.inspect_image(&image).await {
+        if e.to_string().contains("404") {
+            pull_image(host, &image, None).await?;
+        } else {
+            return Err(DockerError::Connection(e));
+        }
+    }
+
+    let mut exposed_ports = HashMap::new();
+    let mut port_bindings: PortMap = HashMap::new();
+    for port in &service.ports {
+        let (container_port, binding) = parse_compose_port(port)?;
+        exposed_ports.insert(container_port.clone(), HashMap::new());
+        port_bindings
+            .entry(container_port)
+            .or_insert_with(|| Some(Vec::new()))
+            .get_or_insert_with(Vec::new)
+            .push(binding);
+    }
+
+    let mut binds = Vec::new();
+    let mut anonymous_volumes = HashMap::new();
+    let mut tmpfs = HashMap::new();
+    for volume in &service.volumes {
+        match compose_volume_mount(project, volume, compose, working_dir)? {
+            VolumeMount::Bind(bind) => binds.push(bind),
+            VolumeMount::Anonymous(target) => {
+                anonymous_volumes.insert(target, HashMap::new());
+            }
+            VolumeMount::Tmpfs(target, options) => {
+                tmpfs.insert(target, options);
+            }
+        }
+    }
+
+    let mut labels: HashMap<String, String> = service.labels.to_map().into_iter().collect();
+    labels.insert(COMPOSE_PROJECT_LABEL.to_string(), project.to_string());
+    labels.insert(COMPOSE_SERVICE_LABEL.to_string(), service_name.to_string());
+    labels.insert(
+        COMPOSE_CONFIG_FILES_LABEL.to_string(),
+        config_file.to_string_lossy().to_string(),
+    );
+    labels.insert(
+        COMPOSE_WORKING_DIR_LABEL.to_string(),
+        working_dir.to_string_lossy().to_string(),
+    );
+    labels.insert(COMPOSE_CONTAINER_NUMBER_LABEL.to_string(), "1".to_string());
+    labels.insert(COMPOSE_ONEOFF_LABEL.to_string(), "False".to_string());
+
+    // The daemon only accepts one endpoint at create time; the rest are connected afterwards
+    let networks: Vec<(String, Vec<String>)> = service
+        .networks
+        .to_map()
+        .into_iter()
+        .map(|(key, mut aliases)| {
+            aliases.insert(0, service_name.to_string());
+            (network_name(project, &key, compose), aliases)
+        })
+        .collect();
+    let (primary_network, primary_aliases) = networks[0].clone();
+
+    let config = ContainerCreateBody {
+        image: Some(image.clone()),
+        cmd: service.command.as_ref().map(command_to_vec),
+        entrypoint: service.entrypoint.as_ref().map(command_to_vec),
+        env: Some(service_environment(service, working_dir)?),
+        labels: Some(labels),
+        exposed_ports: Some(exposed_ports),
+        volumes: Some(anonymous_volumes),
+        user: service.user.clone(),
+        working_dir: service.working_dir.clone(),
+        host_config: Some(HostConfig {
+            binds: Some(binds),
+            tmpfs: (!tmpfs.is_empty()).then_some(tmpfs),
+            port_bindings: Some(port_bindings),
+            restart_policy: service
+                .restart
+                .as_deref()
+                .map(parse_restart_policy)
+                .transpose()?,
+            network_mode: Some(primary_network.clone()),
+            ..Default::default()
+        }),
+        networking_config: Some(NetworkingConfig {
+            endpoints_config: Some(HashMap::from([(
+                primary_network,
+                EndpointSettings {
+                    aliases: Some(primary_aliases),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    };
+
+    let created = client
+        .create_container(
+            Some(CreateContainerOptions {
+                name: Some(container_name.to_string()),
+                ..Default::default()
+            }),
+            config,
+        )
+        .await
+        .map_err(|e| compose_error(project, format!("create container {container_name}"), e))?;
+
+    for (network, aliases) in networks.into_iter().skip(1) {
+        client
+            .connect_network(
+                &network,
+                NetworkConnectRequest {
+                    container: Some(created.id.clone()),
+                    endpoint_config: Some(EndpointSettings {
+                        aliases: Some(aliases),
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await
+            .map_err(|e| {
+                compose_error(project, format!("connect {container_name} to {network}"), e)
+            })?;
+    }
+
+    Ok(created.id)
+}
+
+/// Orders services so that every service comes after the ones it depends on
+fn service_start_order(compose: &ComposeFile) -> Result<Vec<String>> {
+    let mut remaining: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    for (name, service) in &compose.services {
+        let dependencies: BTreeSet<String> = service.depends_on.services().into_iter().collect();
+        if let Some(unknown) = dependencies
+            .iter()
+            .find(|d| !compose.services.contains_key(*d))
+        {
+            return Err(DockerError::InvalidInput {
+                message: format!("Service '{name}' depends on undefined service '{unknown}'"),
+            });
+        }
+        remaining.insert(name.as_str(), dependencies);
+    }
+
+    let mut order = Vec::new();
+    while !remaining.is_empty() {
+        let ready: Vec<&str> = remaining
+            .iter()
+            .filter(|(_, deps)| deps.is_empty())
+            .map(|(name, _)| *name)
+            .collect();
+        if ready.is_empty() {
+            let cycle: Vec<&str> = remaining.keys().copied().collect();
+            return Err(DockerError::InvalidInput {
+                message: format!("Circular depends_on between services: {}", cycle.join(", ")),
+            });
+        }
+        for name in ready {
+            remaining.remove(name);
+            for deps in remaining.values_mut() {
+                deps.remove(name);
+            }
+            order.push(name.to_string());
+        }
+    }
+
+    Ok(order)
+}
+
+/// The networks the services use, including the implicit `default` one for
+/// services without `networks:`. Like compose, declared networks no service
+/// uses are left alone, and services may not refer to undeclared ones.
+fn service_network_keys(compose: &ComposeFile) -> Result<BTreeSet<String>> {
+    let mut network_keys = BTreeSet::new();
+    for (name, service) in &compose.services {
+        for key in service.networks.to_map().into_keys() {
+            if key != "default" && !compose.networks.contains_key(&key) {
+                return Err(DockerError::InvalidInput {
+                    message: format!("Service '{name}' refers to undefined network '{key}'"),
+                });
+            }
+            network_keys.insert(key);
+        }
+    }
+    Ok(network_keys)
+}
+
+fn network_name(project: &str, key: &str, compose: &ComposeFile) -> String {
+    match compose.networks.get(key).cloned().flatten() {
+        Some(ComposeNetworkSpec {
+            name: Some(name), ..
+        }) => name,
+        Some(ComposeNetworkSpec { external: true, .. }) => key.to_string(),
+        _ => format!("{project}_{key}"),
+    }
+}
+
+fn volume_name(project: &str, key: &str, compose: &ComposeFile) -> String {
+    match compose.volumes.get(key).cloned().flatten() {
+        Some(ComposeVolumeSpec {
+            name: Some(name), ..
+        }) => name,
+        Some(ComposeVolumeSpec { external: true, .. }) => key.to_string(),
+        _ => format!("{project}_{key}"),
+    }
+}
+
+/// Parses a compose file, substituting variable references in its values
+fn parse_compose_file(
+    contents: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<ComposeFile> {
+    let invalid = |e: serde_yaml::Error| DockerError::InvalidInput {
+        message: e.to_string(),
+    };
+    let mut value: serde_yaml::Value = serde_yaml::from_str(contents).map_err(invalid)?;
+    interpolate_value(&mut value, lookup)?;
+    serde_yaml::from_value(value).map_err(invalid)
+}
+
+fn interpolate_value(
+    value: &mut serde_yaml::Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<()> {
+    match value {
+        serde_yaml::Value::String(text) => *text = interpolate(text, lookup)?,
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                interpolate_value(item, lookup)?;
+            }
+        }
+        serde_yaml::Value::Mapping(entries) => {
+            for (_, item) in entries.iter_mut() {
+                interpolate_value(item, lookup)?;
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => interpolate_value(&mut tagged.value, lookup)?,
+        _ => {}
+    }
     Ok(())
 }
+
+/// Substitutes `$VAR`, `${VAR}` and the `${VAR:-default}`, `${VAR-default}`,
+/// `${VAR:?error}`, `${VAR?error}`, `${VAR:+alternative}` and
+/// `${VAR+alternative}` forms the way `docker compose` does. `$$` is a
+/// literal `$`, and unset variables without a default become empty.
+fn interpolate(text: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find('$') {
+        result.push_str(&rest[..position]);
+        rest = &rest[position + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            result.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let end = closing_brace(after).ok_or_else(|| DockerError::InvalidInput {
+                message: format!("Unterminated variable reference in '{text}'"),
+            })?;
+            result.push_str(&substitute(&after[..end], lookup)?);
+            rest = &after[end + 1..];
+        } else {
+            let name_len = variable_name_len(rest);
+            if name_len == 0 {
+                result.push('$');
+            } else {
+                result.push_str(&lookup(&rest[..name_len]).unwrap_or_default());
+                rest = &rest[name_len..];
+            }
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Resolves the inside of a `${...}` reference
+fn substitute(expression: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+    let (name, modifier) = expression.split_at(variable_name_len(expression));
+    if name.is_empty() {
+        return Err(DockerError::InvalidInput {
+            message: format!("Invalid variable reference '${{{expression}}}'"),
+        });
+    }
+    let value = lookup(name);
+    if modifier.is_empty() {
+        return Ok(value.unwrap_or_default());
+    }
+
+    // With a colon the variable must also be non-empty to count as set
+    let (set, operator) = match modifier.strip_prefix(':') {
+        Some(operator) => (value.as_deref().is_some_and(|v| !v.is_empty()), operator),
+        None => (value.is_some(), modifier),
+    };
+    let argument = operator.get(1..).unwrap_or_default();
+    match (operator.chars().next(), set) {
+        (Some('-'), true) | (Some('?'), true) => Ok(value.unwrap_or_default()),
+        (Some('-'), false) => interpolate(argument, lookup),
+        (Some('?'), false) => Err(DockerError::InvalidInput {
+            message: format!(
+                "Required variable {name} is not set: {}",
+                interpolate(argument, lookup)?
+            ),
+        }),
+        (Some('+'), true) => interpolate(argument, lookup),
+        (Some('+'), false) => Ok(String::new()),
+        _ => Err(DockerError::InvalidInput {
+            message: format!("Invalid variable reference '${{{expression}}}'"),
+        }),
+    }
+}
+
+/// Length of the variable name at the start of `text`
+fn variable_name_len(text: &str) -> usize {
+    if text.starts_with(|c: char| c.is_ascii_digit()) {
+        return 0;
+    }
+    text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+/// Index of the `}` closing a reference whose `{` was just before `text`
+fn closing_brace(text: &str) -> Option<usize> {
+    let mut depth = 1;
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses `KEY=value` lines the way `docker compose` reads `.env` and
+/// `env_file` files: blank lines and `#` comments are skipped, an `export`
+/// prefix is allowed, and quotes around a value are removed. A bare `KEY`
+/// takes its value from the host environment and is dropped if unset there.
+fn parse_env_file(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.strip_prefix("export ").unwrap_or(line).trim_start())
+        .filter_map(|line| match line.split_once('=') {
+            Some((key, value)) => Some((key.trim().to_string(), env_file_value(value.trim()))),
+            None => std::env::var(line)
+                .ok()
+                .map(|value| (line.to_string(), value)),
+        })
+        .collect()
+}
+
+fn env_file_value(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    // An unquoted value ends at a ` #` comment
+    match value.find(" #") {
+        Some(end) => value[..end].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+/// The service's `KEY=value` environment: its `env_file`s in order, then
+/// `environment`, each overriding what came before
+fn service_environment(service: &ComposeServiceSpec, working_dir: &Path) -> Result<Vec<String>> {
+    let mut environment = BTreeMap::new();
+    for (path, required) in service.env_file.to_list() {
+        let path = working_dir.join(path);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => environment.extend(parse_env_file(&contents)),
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(DockerError::InvalidInput {
+                    message: format!("Could not read env_file {}: {e}", path.display()),
+                })
+            }
+        }
+    }
+    environment.extend(service.environment.to_map());
+    Ok(environment
+        .into_iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect())
+}
+
+/// Where a service volume ends up in the container's config
+#[derive(Debug, PartialEq)]
+enum VolumeMount {
+    Bind(String),          // `source:target[:mode]`, for `HostConfig.binds`
+    Anonymous(String),     // the target of a volume the daemon creates
+    Tmpfs(String, String), // the target and its mount options
+}
+
+/// Converts a service volume into a bind string, an anonymous volume or a
+/// tmpfs mount. Volume types with no engine equivalent here are rejected
+/// rather than mounted as something else.
+fn compose_volume_mount(
+    project: &str,
+    volume: &ComposeServiceVolume,
+    compose: &ComposeFile,
+    working_dir: &Path,
+) -> Result<VolumeMount> {
+    let (source, target, mode) = match volume {
+        ComposeServiceVolume::Short(spec) => {
+            let parts: Vec<&str> = spec.split(':').collect();
+            match parts.as_slice() {
+                [target] => return Ok(VolumeMount::Anonymous(target.to_string())),
+                [source, target] => (source.to_string(), target.to_string(), None),
+                [source, target, mode] => (
+                    source.to_string(),
+                    target.to_string(),
+                    Some(mode.to_string()),
+                ),
+                _ => {
+                    return Err(DockerError::InvalidInput {
+                        message: format!("Invalid volume specification '{spec}'"),
+                    })
+                }
+            }
+        }
+        ComposeServiceVolume::Long {
+            r#type,
+            source,
+            target,
+            read_only,
+            tmpfs,
+        } => match (r#type.as_deref(), source) {
+            (Some("tmpfs"), _) => {
+                let tmpfs = tmpfs.clone().unwrap_or_default();
+                let options: Vec<String> = [
+                    read_only.then(|| "ro".to_string()),
+                    tmpfs.size.map(|size| format!("size={size}")),
+                    tmpfs.mode.map(|mode| format!("mode={mode}")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                return Ok(VolumeMount::Tmpfs(target.clone(), options.join(",")));
+            }
+            (Some(r#type @ ("npipe" | "cluster" | "image")), _) => {
+                return Err(DockerError::InvalidInput {
+                    message: format!("Volume type '{type}' for {target} is not supported"),
+                })
+            }
+            (Some(r#type), _) if r#type != "bind" && r#type != "volume" => {
+                return Err(DockerError::InvalidInput {
+                    message: format!("Unknown volume type '{type}' for {target}"),
+                })
+            }
+            (_, Some(source)) => (
+                source.clone(),
+                target.clone(),
+                read_only.then(|| "ro".to_string()),
+            ),
+            (_, None) => return Ok(VolumeMount::Anonymous(target.clone())),
+        },
+    };
+
+    let source = if source.starts_with('.') || source.starts_with('/') || source.starts_with('~') {
+        // Bind mount: resolve relative to the compose file's directory
+        let expanded = match source.strip_prefix('~') {
+            Some(rest) => std::env::var("HOME")
+                .map(|home| format!("{home}{rest}"))
+                .unwrap_or(source),
+            None => source,
+        };
+        working_dir
+            .join(expanded.trim_start_matches("./"))
+            .to_string_lossy()
+            .to_string()
+    } else if compose.volumes.contains_key(&source) {
+        volume_name(project, &source, compose)
+    } else {
+        return Err(DockerError::InvalidInput {
+            message: format!("Named volume '{source}' is not declared in the compose file"),
+        });
+    };
+
+    Ok(VolumeMount::Bind(match mode {
+        Some(mode) => format!("{source}:{target}:{mode}"),
+        None => format!("{source}:{target}"),
+    }))
+}
+
+/// Parses a compose port into the container port key (e.g. `80/tcp`) and
+/// its host binding. Without a published port, the daemon picks an
+/// ephemeral one, as with `docker compose`.
+fn parse_compose_port(port: &ComposePort) -> Result<(String, PortBinding)> {
+    match port {
+        ComposePort::Number(target) => Ok((
+            format!("{target}/tcp"),
+            PortBinding {
+                host_ip: None,
+                host_port: Some(String::new()),
+            },
+        )),
+        ComposePort::Long {
+            target,
+            published,
+            host_ip,
+            protocol,
+        } => Ok((
+            format!("{target}/{}", protocol.as_deref().unwrap_or("tcp")),
+            PortBinding {
+                host_ip: host_ip.clone(),
+                host_port: Some(
+                    published
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                ),
+            },
+        )),
+        ComposePort::Short(spec) => {
+            let (mapping, protocol) = spec.split_once('/').unwrap_or((spec.as_str(), "tcp"));
+            // Split from the right so IPv6 host addresses keep their colons
+            let mut parts = mapping.rsplitn(3, ':');
+            let container_port = parts.next().unwrap_or_default();
+            let host_port = parts.next();
+            let host_ip = parts
+                .next()
+                .map(|ip| ip.trim_start_matches('[').trim_end_matches(']').to_string())
+                .filter(|ip| !ip.is_empty());
+
+            if container_port.parse::<u16>().is_err() {
+                return Err(DockerError::InvalidInput {
+                    message: format!("Unsupported port specification '{spec}'"),
+                });
+            }
+
+            Ok((
+                format!("{container_port}/{protocol}"),
+                PortBinding {
+                    host_ip,
+                    host_port: Some(host_port.unwrap_or_default().to_string()),
+                },
+            ))
+        }
+    }
+}
+
+fn parse_restart_policy(restart: &str) -> Result<RestartPolicy> {
+    let (name, retries) = match restart.split_once(':') {
+        Some((name, retries)) => (name, retries.parse::<i64>().ok()),
+        None => (restart, None),
+    };
+    let name = name
+        .parse::<RestartPolicyNameEnum>()
+        .map_err(|e| DockerError::InvalidInput {
+            message: format!("Invalid restart policy '{restart}': {e}"),
+        })?;
+
+    Ok(RestartPolicy {
+        name: Some(name),
+        maximum_retry_count: retries,
+    })
+}
+
+fn command_to_vec(command: &ComposeCommand) -> Vec<String> {
+    match command {
+        ComposeCommand::Exec(args) => args.clone(),
+        ComposeCommand::Shell(line) => split_shell_words(line),
+    }
+}
+
+/// Splits a command line on whitespace, honouring single/double quotes and backslash escapes
+fn split_shell_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+
+    words
+}
+
+/// Project names may only contain lowercase letters, digits, dashes and underscores
+fn normalize_project_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect()
+}
+
+fn compose_error(project: &str, action: String, e: bollard::errors::Error) -> DockerError {
+    log_docker_operation("compose_up", false, Some(&e.to_string()));
+    DockerError::OperationFailed {
+        message: format!("Compose project {project}: failed to {action}: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compose_file(yaml: &str) -> ComposeFile {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn binding(ip: Option<&str>, port: &str) -> PortBinding {
+        PortBinding {
+            host_ip: ip.map(str::to_string),
+            host_port: Some(port.to_string()),
+        }
+    }
+
+    #[test]
+    fn ports() {
+        let file = compose_file(
+            r#"
+services:
+  web:
+    image: nginx
+    ports:
+      - 80
+      - "8080:80"
+      - "53:53/udp"
+      - "127.0.0.1:8443:443"
+      - "[::1]:9000:9000"
+      - "9090"
+      - target: 5432
+        published: 15432
+        host_ip: 0.0.0.0
+      - target: 8125
+        host_ip: 127.0.0.1
+      - target: 6379
+        protocol: udp
+"#,
+        );
+        let expected = [
+            ("80/tcp", binding(None, "")),
+            ("80/tcp", binding(None, "8080")),
+            ("53/udp", binding(None, "53")),
+            ("443/tcp", binding(Some("127.0.0.1"), "8443")),
+            ("9000/tcp", binding(Some("::1"), "9000")),
+            ("9090/tcp", binding(None, "")),
+            ("5432/tcp", binding(Some("0.0.0.0"), "15432")),
+            ("8125/tcp", binding(Some("127.0.0.1"), "")),
+            ("6379/udp", binding(None, "")),
+        ];
+        let ports = &file.services["web"].ports;
+        assert_eq!(ports.len(), expected.len());
+        for (port, (key, binding)) in ports.iter().zip(expected) {
+            assert_eq!(
+                parse_compose_port(port).unwrap(),
+                (key.to_string(), binding)
+            );
+        }
+    }
+
+    #[test]
+    fn port_ranges_are_rejected() {
+        let port = ComposePort::Short("8000-8010:8000-8010".to_string());
+        assert!(matches!(
+            parse_compose_port(&port),
+            Err(DockerError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn volume_binds() {
+        let file = compose_file(
+            r#"
+services:
+  db:
+    image: postgres
+    volumes:
+      - ./init:/docker-entrypoint-initdb.d:ro
+      - /srv/backups:/backups
+      - pgdata:/var/lib/postgresql/data
+      - /tmp/cache
+      - type: volume
+        source: pgdata
+        target: /snapshots
+        read_only: true
+      - type: tmpfs
+        target: /run
+      - type: tmpfs
+        target: /scratch
+        read_only: true
+        tmpfs:
+          size: 64m
+          mode: 1777
+volumes:
+  pgdata:
+"#,
+        );
+        let working_dir = Path::new("/work/app");
+        let mounts: Vec<VolumeMount> = file.services["db"]
+            .volumes
+            .iter()
+            .map(|volume| compose_volume_mount("proj", volume, &file, working_dir).unwrap())
+            .collect();
+        assert_eq!(
+            mounts,
+            [
+                VolumeMount::Bind("/work/app/init:/docker-entrypoint-initdb.d:ro".to_string()),
+                VolumeMount::Bind("/srv/backups:/backups".to_string()),
+                VolumeMount::Bind("proj_pgdata:/var/lib/postgresql/data".to_string()),
+                VolumeMount::Anonymous("/tmp/cache".to_string()),
+                VolumeMount::Bind("proj_pgdata:/snapshots:ro".to_string()),
+                VolumeMount::Tmpfs("/run".to_string(), String::new()),
+                VolumeMount::Tmpfs("/scratch".to_string(), "ro,size=64m,mode=1777".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_volume_types_are_rejected() {
+        let file = compose_file(
+            r#"
+services:
+  app:
+    image: app
+    volumes:
+      - type: npipe
+        source: \\.\pipe\docker_engine
+        target: \\.\pipe\docker_engine
+      - type: cluster
+        source: shared
+        target: /shared
+      - type: weird
+        target: /weird
+"#,
+        );
+        for volume in &file.services["app"].volumes {
+            assert!(matches!(
+                compose_volume_mount("proj", volume, &file, Path::new("/work")),
+                Err(DockerError::InvalidInput { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn undeclared_volumes_are_rejected() {
+        let file = compose_file("services: {}");
+        let volume = ComposeServiceVolume::Short("cache:/cache".to_string());
+        assert!(matches!(
+            compose_volume_mount("proj", &volume, &file, Path::new("/work")),
+            Err(DockerError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn interpolation() {
+        let lookup = |name: &str| match name {
+            "TAG" => Some("1.25".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+        for (text, expected) in [
+            ("nginx:${TAG}", "nginx:1.25"),
+            ("nginx:$TAG-alpine", "nginx:1.25-alpine"),
+            ("${MISSING}", ""),
+            ("${MISSING:-8080}:80", "8080:80"),
+            ("${EMPTY:-fallback}", "fallback"),
+            ("${EMPTY-fallback}", ""),
+            ("${MISSING:-${TAG}}", "1.25"),
+            ("${TAG:+set}", "set"),
+            ("${EMPTY:+set}", ""),
+            ("${EMPTY+set}", "set"),
+            ("${TAG:?required}", "1.25"),
+            ("cost $$5 and $1", "cost $5 and $1"),
+        ] {
+            assert_eq!(interpolate(text, &lookup).unwrap(), expected, "{text}");
+        }
+        for text in ["${MISSING:?set it}", "${EMPTY:?}", "${TAG", "${}"] {
+            assert!(
+                matches!(
+                    interpolate(text, &lookup),
+                    Err(DockerError::InvalidInput { .. })
+                ),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn compose_file_values_are_interpolated() {
+        let lookup = |name: &str| (name == "PORT").then(|| "8080".to_string());
+        let file = parse_compose_file(
+            r#"
+# ${NOT_IN_A_VALUE:?comments are left alone}
+services:
+  web:
+    image: "nginx:${TAG:-latest}"
+    ports:
+      - "${PORT}:80"
+"#,
+            &lookup,
+        )
+        .unwrap();
+        let web = &file.services["web"];
+        assert_eq!(web.image.as_deref(), Some("nginx:latest"));
+        assert_eq!(
+            parse_compose_port(&web.ports[0]).unwrap(),
+            ("80/tcp".to_string(), binding(None, "8080"))
+        );
+    }
+
+    #[test]
+    fn env_files() {
+        let vars = parse_env_file(
+            r#"
+# database
+DB_HOST=db
+export DB_PORT = 5432
+DB_PASSWORD="s3cret # not a comment"
+GREETING='hello world'
+DEBUG=true # a comment
+EMPTY=
+"#,
+        );
+        assert_eq!(
+            vars,
+            BTreeMap::from(
+                [
+                    ("DB_HOST", "db"),
+                    ("DB_PORT", "5432"),
+                    ("DB_PASSWORD", "s3cret # not a comment"),
+                    ("GREETING", "hello world"),
+                    ("DEBUG", "true"),
+                    ("EMPTY", ""),
+                ]
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+            )
+        );
+
+        let dir = std::env::temp_dir().join(format!("compose-env-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.env"), "LEVEL=debug\nPORT=3000\n").unwrap();
+        let file = compose_file(
+            r#"
+services:
+  app:
+    image: app
+    env_file:
+      - app.env
+      - path: missing.env
+        required: false
+    environment:
+      PORT: 4000
+"#,
+        );
+        let environment = service_environment(&file.services["app"], &dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(environment.unwrap(), ["LEVEL=debug", "PORT=4000"]);
+
+        let file = compose_file("services: {app: {image: app, env_file: missing.env}}");
+        assert!(matches!(
+            service_environment(&file.services["app"], Path::new("/nonexistent")),
+            Err(DockerError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn shell_words() {
+        for (line, words) in [
+            ("npm run start", vec!["npm", "run", "start"]),
+            ("  spaced   out  ", vec!["spaced", "out"]),
+            (
+                r#"sh -c "echo 'hi there'""#,
+                vec!["sh", "-c", "echo 'hi there'"],
+            ),
+            ("echo 'a \\ b'", vec!["echo", "a \\ b"]),
+            (r#"echo "a \" b""#, vec!["echo", "a \" b"]),
+            (r"one\ word", vec!["one word"]),
+            ("empty '' arg", vec!["empty", "", "arg"]),
+            ("", vec![]),
+        ] {
+            assert_eq!(split_shell_words(line), words, "{line}");
+        }
+    }
+
+    #[test]
+    fn start_order_follows_dependencies() {
+        let file = compose_file(
+            r#"
+services:
+  web:
+    image: app
+    depends_on: [api]
+  api:
+    image: app
+    depends_on:
+      db:
+        condition: service_healthy
+      cache:
+        condition: service_started
+  db:
+    image: postgres
+  cache:
+    image: redis
+"#,
+        );
+        assert_eq!(
+            service_start_order(&file).unwrap(),
+            ["cache", "db", "api", "web"]
+        );
+    }
+
+    #[test]
+    fn dependency_cycles_are_rejected() {
+        let file = compose_file(
+            r#"
+services:
+  a:
+    image: app
+    depends_on: [b]
+  b:
+    image: app
+    depends_on: [c]
+  c:
+    image: app
+    depends_on: [a]
+  d:
+    image: app
+"#,
+        );
+        match service_start_order(&file) {
+            Err(DockerError::InvalidInput { message }) => {
+                assert!(message.contains("a, b, c"), "{message}")
+            }
+            other => panic!("expected a cycle error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn undefined_dependencies_are_rejected() {
+        let file = compose_file("services:\n  web:\n    image: app\n    depends_on: [db]\n");
+        assert!(matches!(
+            service_start_order(&file),
+            Err(DockerError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn service_networks() {
+        let file = compose_file(
+            r#"
+services:
+  web:
+    image: app
+    networks: [front]
+  worker:
+    image: app
+networks:
+  front:
+  back:
+"#,
+        );
+        assert_eq!(
+            service_network_keys(&file).unwrap(),
+            BTreeSet::from(["default", "front"].map(str::to_string))
+        );
+
+        let file = compose_file("services:\n  web:\n    image: app\n    networks: [missing]\n");
+        assert!(matches!(
+            service_network_keys(&file),
+            Err(DockerError::InvalidInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,1490 @@
+use crate::docker::client::DOCKER_CLIENT;
+use crate::docker::container_spec::{
+    ContainerResources, ContainerSpec, MountSpec, NetworkAttachment, PortMapping, RestartPolicySpec,
+};
+use crate::utils::{log_docker_operation, DockerError, Result};
+use bollard::models::{ContainerInspectResponse, DeviceMapping, HealthConfig, ImageConfig};
+use bollard::query_parameters::InspectContainerOptions;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+/// A container's configuration in reusable forms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerDefinition {
+    pub spec: ContainerSpec,
+    pub options: ContainerRuntimeOptions,
+    pub unexported: Vec<String>, // `docker run` flags in use that neither form reproduces
+    pub run_command: String,     // `docker run ...`, plus `docker network connect` lines
+    pub compose: String,         // a compose file with the container as its only service
+}
+
+/// Settings beyond `ContainerSpec` that the export carries over. Values the
+/// daemon would pick anyway are left unset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerRuntimeOptions {
+    pub hostname: Option<String>,
+    pub domainname: Option<String>,
+    #[serde(default)]
+    pub privileged: bool,
+    #[serde(default)]
+    pub auto_remove: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub init: bool,
+    #[serde(default)]
+    pub tty: bool,
+    #[serde(default)]
+    pub interactive: bool, // stdin kept open
+    #[serde(default)]
+    pub cap_add: Vec<String>,
+    #[serde(default)]
+    pub cap_drop: Vec<String>,
+    #[serde(default)]
+    pub devices: Vec<String>, // host_path[:container_path[:permissions]]
+    #[serde(default)]
+    pub extra_hosts: Vec<String>, // host:ip
+    #[serde(default)]
+    pub dns: Vec<String>,
+    #[serde(default)]
+    pub dns_search: Vec<String>,
+    #[serde(default)]
+    pub dns_options: Vec<String>,
+    #[serde(default)]
+    pub security_opt: Vec<String>,
+    #[serde(default)]
+    pub group_add: Vec<String>,
+    #[serde(default)]
+    pub sysctls: BTreeMap<String, String>,
+    #[serde(default)]
+    pub ulimits: Vec<UlimitSpec>,
+    #[serde(default)]
+    pub tmpfs: BTreeMap<String, String>, // target to mount options, which may be empty
+    pub shm_size: Option<i64>, // bytes
+    pub ipc_mode: Option<String>,
+    pub pid_mode: Option<String>,
+    pub log_driver: Option<String>,
+    #[serde(default)]
+    pub log_options: BTreeMap<String, String>,
+    pub healthcheck: Option<HealthcheckSpec>,
+    pub stop_signal: Option<String>,
+    pub stop_timeout: Option<i64>, // seconds
+    pub runtime: Option<String>,
+    pub memory_reservation: Option<i64>, // bytes
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UlimitSpec {
+    pub name: String,
+    pub soft: i64,
+    pub hard: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthcheckSpec {
+    pub test: Vec<String>,     // ["CMD", ...], ["CMD-SHELL", cmd] or ["NONE"]
+    pub interval: Option<i64>, // nanoseconds, as are the other durations
+    pub timeout: Option<i64>,
+    pub retries: Option<i64>,
+    pub start_period: Option<i64>,
+    pub start_interval: Option<i64>,
+}
+
+// Labels the compose CLI puts on its containers; they describe the old project
+const COMPOSE_LABEL_PREFIX: &str = "com.docker.compose.";
+
+const DEFAULT_LOG_DRIVER: &str = "json-file";
+const DEFAULT_SHM_SIZE: i64 = 64 * 1024 * 1024;
+
+/// Reverse-engineers a container into the `docker run` command line and the
+/// compose service that would recreate it. Settings equal to the image's own
+/// defaults are left out to keep both readable; settings neither form can
+/// express are listed in `unexported` and noted at the top of both.
+pub async fn export_container_definition(
+    host: Option<&str>,
+    id: &str,
+) -> Result<ContainerDefinition> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let container = match client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await
+    {
+        Ok(container) => container,
+        Err(e) => {
+            log_docker_operation("export_container_definition", false, Some(&e.to_string()));
+            return if e.to_string().contains("404") {
+                Err(DockerError::ContainerNotFound { id: id.to_string() })
+            } else {
+                Err(DockerError::Connection(e))
+            };
+        }
+    };
+
+    // Without the image (e.g. it was removed since) nothing counts as default
+    let image_config = match &container.image {
+        Some(image_id) => client
+            .inspect_image(image_id)
+            .await
+            .ok()
+            .and_then(|image| image.config),
+        None => None,
+    };
+    let image_config = image_config.unwrap_or_default();
+
+    let default_log_driver = client
+        .info()
+        .await
+        .ok()
+        .and_then(|info| info.logging_driver)
+        .unwrap_or_else(|| DEFAULT_LOG_DRIVER.to_string());
+
+    let spec = spec_from_inspect(&container, &image_config);
+    let options = options_from_inspect(&container, &image_config, &default_log_driver);
+    let unexported = unexported_settings(&container);
+    let definition = ContainerDefinition {
+        run_command: run_command(&spec, &options, &unexported),
+        compose: compose_service(&spec, &options, &unexported)?,
+        spec,
+        options,
+        unexported,
+    };
+
+    log_docker_operation(
+        "export_container_definition",
+        true,
+        Some(&format!("Exported definition of container {id}")),
+    );
+    Ok(definition)
+}
+
+fn spec_from_inspect(container: &ContainerInspectResponse, image: &ImageConfig) -> ContainerSpec {
+    let config = container.config.clone().unwrap_or_default();
+    let host_config = container.host_config.clone().unwrap_or_default();
+    let id = container.id.clone().unwrap_or_default();
+    let short_id: String = id.chars().take(12).collect();
+    let name = container
+        .name
+        .clone()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+
+    // An explicit entrypoint resets the image's CMD, so the command has to
+    // be spelled out whenever the entrypoint is
+    let entrypoint = config
+        .entrypoint
+        .clone()
+        .filter(|entrypoint| Some(entrypoint) != image.entrypoint.as_ref());
+    let command = config
+        .cmd
+        .clone()
+        .filter(|cmd| entrypoint.is_some() || Some(cmd) != image.cmd.as_ref())
+        .filter(|cmd| !cmd.is_empty());
+
+    let image_env = image.env.clone().unwrap_or_default();
+    let env = config
+        .env
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|var| !image_env.contains(var))
+        .collect();
+
+    let image_labels = image.labels.clone().unwrap_or_default();
+    let labels = config
+        .labels
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(key, value)| {
+            !key.starts_with(COMPOSE_LABEL_PREFIX) && image_labels.get(key) != Some(value)
+        })
+        .collect();
+
+    let mut ports: Vec<PortMapping> = host_config
+        .port_bindings
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(key, bindings)| {
+            let (port, protocol) = key.split_once('/').unwrap_or((key.as_str(), "tcp"));
+            let container_port = port.parse().unwrap_or_default();
+            let protocol = protocol.to_string();
+            bindings
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |binding| PortMapping {
+                    container_port,
+                    protocol: protocol.clone(),
+                    host_ip: binding.host_ip.filter(|ip| !ip.is_empty()),
+                    host_port: binding.host_port.and_then(|port| port.parse().ok()),
+                })
+        })
+        .collect();
+    ports.sort_by_key(|port| (port.container_port, port.host_port));
+
+    // `--tmpfs` mounts are not listed here; they are runtime options
+    let image_volumes = image.volumes.clone().unwrap_or_default();
+    let mut mounts: Vec<MountSpec> = Vec::new();
+    for mount in container.mounts.iter().flatten() {
+        let Some(target) = mount.destination.clone() else {
+            continue;
+        };
+        let r#type = mount
+            .typ
+            .as_ref()
+            .map(|typ| typ.to_string())
+            .unwrap_or_default();
+        let source = match r#type.as_str() {
+            "bind" => mount.source.clone(),
+            // Anonymous volumes the image declares come back on their own
+            "volume" if is_anonymous_volume(mount.name.as_deref()) => {
+                if image_volumes.contains_key(&target) {
+                    continue;
+                }
+                None
+            }
+            "volume" => mount.name.clone(),
+            "tmpfs" => None,
+            _ => continue,
+        };
+        mounts.push(MountSpec {
+            r#type,
+            source,
+            target,
+            read_only: !mount.rw.unwrap_or(true),
+        });
+    }
+
+    let network_mode = network_mode(container);
+    let mut networks: Vec<NetworkAttachment> = container
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.networks.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(network, endpoint)| NetworkAttachment {
+            aliases: endpoint
+                .aliases
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|alias| *alias != short_id && *alias != name)
+                .collect(),
+            ipv4_address: endpoint
+                .ipam_config
+                .and_then(|ipam| ipam.ipv4_address)
+                .filter(|address| !address.is_empty()),
+            name: network,
+        })
+        .collect();
+    if network_mode.starts_with("container:") {
+        networks = vec![NetworkAttachment {
+            name: network_mode.clone(),
+            aliases: Vec::new(),
+            ipv4_address: None,
+        }];
+    }
+    networks.sort_by_key(|network| (network.name != network_mode, network.name.clone()));
+    // Plain bridge networking is what every container gets anyway
+    if networks.len() == 1 && networks[0].name == "bridge" && networks[0].aliases.is_empty() {
+        networks.clear();
+    }
+
+    let restart_policy = host_config
+        .restart_policy
+        .as_ref()
+        .and_then(|policy| {
+            let name = policy.name.as_ref()?.to_string();
+            Some(RestartPolicySpec {
+                maximum_retry_count: policy
+                    .maximum_retry_count
+                    .filter(|count| *count > 0 && name == "on-failure"),
+                name,
+            })
+        })
+        .filter(|policy| !policy.name.is_empty() && policy.name != "no");
+
+    let memory = host_config.memory.filter(|memory| *memory > 0);
+    let resources = ContainerResources {
+        memory,
+        // Unset swap is reported as twice the memory limit
+        memory_swap: host_config
+            .memory_swap
+            .filter(|swap| *swap != 0 && Some(*swap) != memory.map(|memory| memory * 2)),
+        cpu_shares: host_config.cpu_shares.filter(|shares| *shares > 0),
+        nano_cpus: host_config.nano_cpus.filter(|cpus| *cpus > 0),
+        cpuset_cpus: host_config
+            .cpuset_cpus
+            .clone()
+            .filter(|cpus| !cpus.is_empty()),
+        pids_limit: host_config.pids_limit.filter(|limit| *limit > 0),
+    };
+
+    ContainerSpec {
+        image: config.image.clone().unwrap_or_default(),
+        name: Some(name).filter(|name| !name.is_empty()),
+        command,
+        entrypoint,
+        env,
+        ports,
+        mounts,
+        networks,
+        restart_policy,
+        labels,
+        resources,
+        user: config
+            .user
+            .clone()
+            .filter(|user| !user.is_empty() && Some(user) != image.user.as_ref()),
+        working_dir: config
+            .working_dir
+            .clone()
+            .filter(|dir| !dir.is_empty() && Some(dir) != image.working_dir.as_ref()),
+    }
+}
+
+fn options_from_inspect(
+    container: &ContainerInspectResponse,
+    image: &ImageConfig,
+    default_log_driver: &str,
+) -> ContainerRuntimeOptions {
+    let config = container.config.clone().unwrap_or_default();
+    let host_config = container.host_config.clone().unwrap_or_default();
+    let short_id: String = container
+        .id
+        .clone()
+        .unwrap_or_default()
+        .chars()
+        .take(12)
+        .collect();
+    let non_empty = |value: Option<String>| value.filter(|value| !value.is_empty());
+
+    // The daemon defaults the hostname to the short id, and a container
+    // sharing another's network can't set one at all
+    let network_mode = network_mode(container);
+    let shares_network = network_mode == "host" || network_mode.starts_with("container:");
+    let hostname = non_empty(config.hostname.clone())
+        .filter(|hostname| *hostname != short_id && !shares_network);
+
+    let log_config = host_config.log_config.clone().unwrap_or_default();
+
+    let healthcheck = config
+        .healthcheck
+        .clone()
+        .filter(|healthcheck| Some(healthcheck) != image.healthcheck.as_ref())
+        .and_then(healthcheck_spec);
+
+    ContainerRuntimeOptions {
+        hostname,
+        domainname: non_empty(config.domainname.clone()).filter(|_| !shares_network),
+        privileged: host_config.privileged.unwrap_or_default(),
+        auto_remove: host_config.auto_remove.unwrap_or_default(),
+        read_only: host_config.readonly_rootfs.unwrap_or_default(),
+        init: host_config.init.unwrap_or_default(),
+        tty: config.tty.unwrap_or_default(),
+        interactive: config.open_stdin.unwrap_or_default(),
+        cap_add: host_config.cap_add.clone().unwrap_or_default(),
+        cap_drop: host_config.cap_drop.clone().unwrap_or_default(),
+        devices: host_config
+            .devices
+            .iter()
+            .flatten()
+            .filter_map(device_string)
+            .collect(),
+        extra_hosts: host_config.extra_hosts.clone().unwrap_or_default(),
+        dns: host_config.dns.clone().unwrap_or_default(),
+        dns_search: host_config.dns_search.clone().unwrap_or_default(),
+        dns_options: host_config.dns_options.clone().unwrap_or_default(),
+        security_opt: host_config.security_opt.clone().unwrap_or_default(),
+        group_add: host_config.group_add.clone().unwrap_or_default(),
+        sysctls: host_config
+            .sysctls
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        ulimits: host_config
+            .ulimits
+            .iter()
+            .flatten()
+            .filter_map(|ulimit| {
+                Some(UlimitSpec {
+                    name: ulimit.name.clone()?,
+                    soft: ulimit.soft?,
+                    hard: ulimit.hard.or(ulimit.soft)?,
+                })
+            })
+            .collect(),
+        tmpfs: host_config
+            .tmpfs
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        shm_size: host_config
+            .shm_size
+            .filter(|size| *size > 0 && *size != DEFAULT_SHM_SIZE),
+        // "shareable" was the default before daemon 18.09
+        ipc_mode: non_empty(host_config.ipc_mode.clone())
+            .filter(|mode| mode != "private" && mode != "shareable"),
+        pid_mode: non_empty(host_config.pid_mode.clone()),
+        log_driver: non_empty(log_config.typ).filter(|driver| driver != default_log_driver),
+        log_options: log_config.config.unwrap_or_default().into_iter().collect(),
+        healthcheck,
+        stop_signal: non_empty(config.stop_signal.clone())
+            .filter(|signal| Some(signal) != image.stop_signal.as_ref()),
+        stop_timeout: config
+            .stop_timeout
+            .filter(|timeout| Some(*timeout) != image.stop_timeout),
+        runtime: non_empty(host_config.runtime.clone()).filter(|runtime| runtime != "runc"),
+        memory_reservation: host_config.memory_reservation.filter(|bytes| *bytes > 0),
+    }
+}
+
+/// `docker run` flags of settings the container uses that the export does not reproduce
+fn unexported_settings(container: &ContainerInspectResponse) -> Vec<String> {
+    let config = container.config.clone().unwrap_or_default();
+    let host_config = container.host_config.clone().unwrap_or_default();
+    fn listed<T>(values: &Option<Vec<T>>) -> bool {
+        values.as_ref().is_some_and(|v| !v.is_empty())
+    }
+    let text = |value: &Option<String>| value.as_ref().is_some_and(|v| !v.is_empty());
+    let positive = |value: Option<i64>| value.is_some_and(|v| v > 0);
+
+    [
+        ("--link", listed(&host_config.links)),
+        ("--volumes-from", listed(&host_config.volumes_from)),
+        ("--gpus", listed(&host_config.device_requests)),
+        (
+            "--device-cgroup-rule",
+            listed(&host_config.device_cgroup_rules),
+        ),
+        ("--cgroup-parent", text(&host_config.cgroup_parent)),
+        (
+            "--oom-score-adj",
+            host_config.oom_score_adj.is_some_and(|adj| adj != 0),
+        ),
+        (
+            "--oom-kill-disable",
+            host_config.oom_kill_disable.unwrap_or_default(),
+        ),
+        (
+            "--blkio-weight",
+            host_config.blkio_weight.is_some_and(|weight| weight > 0),
+        ),
+        (
+            "--blkio-weight-device",
+            listed(&host_config.blkio_weight_device),
+        ),
+        (
+            "--device-read-bps",
+            listed(&host_config.blkio_device_read_bps),
+        ),
+        (
+            "--device-write-bps",
+            listed(&host_config.blkio_device_write_bps),
+        ),
+        (
+            "--device-read-iops",
+            listed(&host_config.blkio_device_read_iops),
+        ),
+        (
+            "--device-write-iops",
+            listed(&host_config.blkio_device_write_iops),
+        ),
+        ("--cpu-period", positive(host_config.cpu_period)),
+        ("--cpu-quota", positive(host_config.cpu_quota)),
+        ("--cpu-rt-period", positive(host_config.cpu_realtime_period)),
+        (
+            "--cpu-rt-runtime",
+            positive(host_config.cpu_realtime_runtime),
+        ),
+        ("--cpuset-mems", text(&host_config.cpuset_mems)),
+        (
+            "--memory-swappiness",
+            host_config
+                .memory_swappiness
+                .is_some_and(|swappiness| swappiness >= 0),
+        ),
+        ("--uts", text(&host_config.uts_mode)),
+        ("--userns", text(&host_config.userns_mode)),
+        (
+            "--storage-opt",
+            host_config
+                .storage_opt
+                .as_ref()
+                .is_some_and(|options| !options.is_empty()),
+        ),
+        ("--mac-address", text(&config.mac_address)),
+        (
+            "--publish-all",
+            host_config.publish_all_ports.unwrap_or_default(),
+        ),
+        ("--volume-driver", text(&host_config.volume_driver)),
+        (
+            "--annotation",
+            host_config
+                .annotations
+                .as_ref()
+                .is_some_and(|annotations| !annotations.is_empty()),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, used)| *used)
+    .map(|(flag, _)| flag.to_string())
+    .collect()
+}
+
+// The network the container was created on, "bridge" when left to the daemon
+fn network_mode(container: &ContainerInspectResponse) -> String {
+    container
+        .host_config
+        .as_ref()
+        .and_then(|host_config| host_config.network_mode.clone())
+        .filter(|mode| !mode.is_empty() && mode != "default")
+        .unwrap_or_else(|| "bridge".to_string())
+}
+
+fn healthcheck_spec(healthcheck: HealthConfig) -> Option<HealthcheckSpec> {
+    let positive = |value: Option<i64>| value.filter(|v| *v > 0);
+    Some(HealthcheckSpec {
+        test: healthcheck.test.filter(|test| !test.is_empty())?,
+        interval: positive(healthcheck.interval),
+        timeout: positive(healthcheck.timeout),
+        retries: positive(healthcheck.retries),
+        start_period: positive(healthcheck.start_period),
+        start_interval: positive(healthcheck.start_interval),
+    })
+}
+
+// Arguments of a `docker run` line, quoted as they are added
+struct CommandLine(Vec<String>);
+
+impl CommandLine {
+    fn switch(&mut self, name: &str) {
+        self.0.push(name.to_string());
+    }
+
+    fn flag(&mut self, name: &str, value: &str) {
+        self.0.push(name.to_string());
+        self.0.push(quote(value));
+    }
+}
+
+fn run_command(
+    spec: &ContainerSpec,
+    options: &ContainerRuntimeOptions,
+    unexported: &[String],
+) -> String {
+    let mut args = CommandLine(vec!["docker".into(), "run".into(), "-d".into()]);
+
+    if let Some(name) = &spec.name {
+        args.flag("--name", name);
+    }
+    if let Some(policy) = &spec.restart_policy {
+        match policy.maximum_retry_count {
+            Some(count) => args.flag("--restart", &format!("{}:{count}", policy.name)),
+            None => args.flag("--restart", &policy.name),
+        }
+    }
+    if let Some(user) = &spec.user {
+        args.flag("--user", user);
+    }
+    if let Some(working_dir) = &spec.working_dir {
+        args.flag("--workdir", working_dir);
+    }
+    for var in &spec.env {
+        args.flag("-e", var);
+    }
+    for port in &spec.ports {
+        args.flag("-p", &port_string(port));
+    }
+    for mount in &spec.mounts {
+        match mount.r#type.as_str() {
+            "tmpfs" => args.flag("--tmpfs", &mount.target),
+            _ => args.flag("-v", &volume_string(mount)),
+        }
+    }
+    for (target, mount_options) in &options.tmpfs {
+        args.flag("--tmpfs", &tmpfs_string(target, mount_options));
+    }
+    if let Some(network) = spec.networks.first() {
+        args.flag("--network", &network.name);
+        for alias in &network.aliases {
+            args.flag("--network-alias", alias);
+        }
+        if let Some(address) = &network.ipv4_address {
+            args.flag("--ip", address);
+        }
+    }
+    let mut labels: Vec<_> = spec.labels.iter().collect();
+    labels.sort();
+    for (key, value) in labels {
+        args.flag("--label", &format!("{key}={value}"));
+    }
+
+    let resources = &spec.resources;
+    if let Some(memory) = resources.memory {
+        args.flag("--memory", &memory.to_string());
+    }
+    if let Some(swap) = resources.memory_swap {
+        args.flag("--memory-swap", &swap.to_string());
+    }
+    if let Some(shares) = resources.cpu_shares {
+        args.flag("--cpu-shares", &shares.to_string());
+    }
+    if let Some(nano_cpus) = resources.nano_cpus {
+        args.flag("--cpus", &cpus_string(nano_cpus));
+    }
+    if let Some(cpuset) = &resources.cpuset_cpus {
+        args.flag("--cpuset-cpus", cpuset);
+    }
+    if let Some(limit) = resources.pids_limit {
+        args.flag("--pids-limit", &limit.to_string());
+    }
+
+    run_options(&mut args, options);
+
+    // --entrypoint takes a single executable; its arguments go before the command
+    let mut command = spec.command.clone().unwrap_or_default();
+    if let Some((executable, arguments)) = spec
+        .entrypoint
+        .as_ref()
+        .and_then(|entrypoint| entrypoint.split_first())
+    {
+        args.flag("--entrypoint", executable);
+        command.splice(0..0, arguments.iter().cloned());
+    }
+
+    args.0.push(quote(&spec.image));
+    args.0.extend(command.iter().map(|arg| quote(arg)));
+
+    let mut lines: Vec<String> = unexported_comment(unexported).into_iter().collect();
+    lines.push(args.0.join(" "));
+    let container = spec
+        .name
+        .clone()
+        .unwrap_or_else(|| "<container>".to_string());
+    for network in spec.networks.iter().skip(1) {
+        let mut connect = vec!["docker network connect".to_string()];
+        for alias in &network.aliases {
+            connect.push(format!("--alias {}", quote(alias)));
+        }
+        if let Some(address) = &network.ipv4_address {
+            connect.push(format!("--ip {}", quote(address)));
+        }
+        connect.push(quote(&network.name));
+        connect.push(quote(&container));
+        lines.push(connect.join(" "));
+    }
+    lines.join("\n")
+}
+
+fn run_options(args: &mut CommandLine, options: &ContainerRuntimeOptions) {
+    if let Some(hostname) = &options.hostname {
+        args.flag("--hostname", hostname);
+    }
+    if let Some(domainname) = &options.domainname {
+        args.flag("--domainname", domainname);
+    }
+    for (enabled, switch) in [
+        (options.privileged, "--privileged"),
+        (options.auto_remove, "--rm"),
+        (options.read_only, "--read-only"),
+        (options.init, "--init"),
+        (options.tty, "-t"),
+        (options.interactive, "-i"),
+    ] {
+        if enabled {
+            args.switch(switch);
+        }
+    }
+    for (flag, values) in [
+        ("--cap-add", &options.cap_add),
+        ("--cap-drop", &options.cap_drop),
+        ("--device", &options.devices),
+        ("--add-host", &options.extra_hosts),
+        ("--dns", &options.dns),
+        ("--dns-search", &options.dns_search),
+        ("--dns-option", &options.dns_options),
+        ("--security-opt", &options.security_opt),
+        ("--group-add", &options.group_add),
+    ] {
+        for value in values {
+            args.flag(flag, value);
+        }
+    }
+    for (key, value) in &options.sysctls {
+        args.flag("--sysctl", &format!("{key}={value}"));
+    }
+    for ulimit in &options.ulimits {
+        args.flag("--ulimit", &ulimit_string(ulimit));
+    }
+    if let Some(size) = options.shm_size {
+        args.flag("--shm-size", &size.to_string());
+    }
+    if let Some(mode) = &options.ipc_mode {
+        args.flag("--ipc", mode);
+    }
+    if let Some(mode) = &options.pid_mode {
+        args.flag("--pid", mode);
+    }
+    if let Some(driver) = &options.log_driver {
+        args.flag("--log-driver", driver);
+    }
+    for (key, value) in &options.log_options {
+        args.flag("--log-opt", &format!("{key}={value}"));
+    }
+    if let Some(healthcheck) = &options.healthcheck {
+        match healthcheck.test.split_first() {
+            Some((kind, _)) if kind == "NONE" => args.switch("--no-healthcheck"),
+            // --health-cmd always runs through the shell
+            Some((kind, test)) => {
+                let command = if kind == "CMD-SHELL" {
+                    test.join(" ")
+                } else {
+                    test.iter()
+                        .map(|arg| quote(arg))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                args.flag("--health-cmd", &command);
+                for (flag, duration) in [
+                    ("--health-interval", healthcheck.interval),
+                    ("--health-timeout", healthcheck.timeout),
+                    ("--health-start-period", healthcheck.start_period),
+                    ("--health-start-interval", healthcheck.start_interval),
+                ] {
+                    if let Some(nanos) = duration {
+                        args.flag(flag, &duration_string(nanos));
+                    }
+                }
+                if let Some(retries) = healthcheck.retries {
+                    args.flag("--health-retries", &retries.to_string());
+                }
+            }
+            None => {}
+        }
+    }
+    if let Some(signal) = &options.stop_signal {
+        args.flag("--stop-signal", signal);
+    }
+    if let Some(timeout) = options.stop_timeout {
+        args.flag("--stop-timeout", &timeout.to_string());
+    }
+    if let Some(runtime) = &options.runtime {
+        args.flag("--runtime", runtime);
+    }
+    if let Some(bytes) = options.memory_reservation {
+        args.flag("--memory-reservation", &bytes.to_string());
+    }
+}
+
+fn compose_service(
+    spec: &ContainerSpec,
+    options: &ContainerRuntimeOptions,
+    unexported: &[String],
+) -> Result<String> {
+    let mut service = Mapping::new();
+    let mut set = |key: &str, value: Value| {
+        service.insert(Value::from(key), value);
+    };
+
+    set("image", spec.image.as_str().into());
+    if let Some(name) = &spec.name {
+        set("container_name", name.as_str().into());
+    }
+    if let Some(entrypoint) = &spec.entrypoint {
+        set("entrypoint", strings(entrypoint));
+    }
+    if let Some(command) = &spec.command {
+        set("command", strings(command));
+    }
+    if let Some(user) = &spec.user {
+        set("user", user.as_str().into());
+    }
+    if let Some(working_dir) = &spec.working_dir {
+        set("working_dir", working_dir.as_str().into());
+    }
+    if !spec.env.is_empty() {
+        set("environment", strings(&spec.env));
+    }
+    if !spec.ports.is_empty() {
+        let ports: Vec<String> = spec.ports.iter().map(port_string).collect();
+        set("ports", strings(&ports));
+    }
+    let volumes: Vec<String> = spec
+        .mounts
+        .iter()
+        .filter(|mount| mount.r#type != "tmpfs")
+        .map(volume_string)
+        .collect();
+    if !volumes.is_empty() {
+        set("volumes", strings(&volumes));
+    }
+    let tmpfs: Vec<String> = spec
+        .mounts
+        .iter()
+        .filter(|mount| mount.r#type == "tmpfs")
+        .map(|mount| mount.target.clone())
+        .chain(
+            options
+                .tmpfs
+                .iter()
+                .map(|(target, mount_options)| tmpfs_string(target, mount_options)),
+        )
+        .collect();
+    if !tmpfs.is_empty() {
+        set("tmpfs", strings(&tmpfs));
+    }
+
+    // host, none and container:<id> are network modes rather than networks
+    let mut external_networks = Vec::new();
+    match spec.networks.first() {
+        Some(network)
+            if network.name == "host"
+                || network.name == "none"
+                || network.name.starts_with("container:") =>
+        {
+            set("network_mode", network.name.as_str().into());
+        }
+        Some(_) => {
+            let mut networks = Mapping::new();
+            for network in &spec.networks {
+                let mut settings = Mapping::new();
+                if !network.aliases.is_empty() {
+                    settings.insert("aliases".into(), strings(&network.aliases));
+                }
+                if let Some(address) = &network.ipv4_address {
+                    settings.insert("ipv4_address".into(), address.as_str().into());
+                }
+                networks.insert(
+                    network.name.as_str().into(),
+                    if settings.is_empty() {
+                        Value::Null
+                    } else {
+                        Value::Mapping(settings)
+                    },
+                );
+                external_networks.push(network.name.clone());
+            }
+            set("networks", Value::Mapping(networks));
+        }
+        None => {}
+    }
+
+    if let Some(policy) = &spec.restart_policy {
+        let restart = match policy.maximum_retry_count {
+            Some(count) => format!("{}:{count}", policy.name),
+            None => policy.name.clone(),
+        };
+        set("restart", restart.into());
+    }
+    if !spec.labels.is_empty() {
+        set("labels", string_map(spec.labels.iter()));
+    }
+
+    let resources = &spec.resources;
+    if let Some(memory) = resources.memory {
+        set("mem_limit", memory.into());
+    }
+    if let Some(swap) = resources.memory_swap {
+        set("memswap_limit", swap.into());
+    }
+    if let Some(shares) = resources.cpu_shares {
+        set("cpu_shares", shares.into());
+    }
+    if let Some(nano_cpus) = resources.nano_cpus {
+        set("cpus", cpus_string(nano_cpus).into());
+    }
+    if let Some(cpuset) = &resources.cpuset_cpus {
+        set("cpuset", cpuset.as_str().into());
+    }
+    if let Some(limit) = resources.pids_limit {
+        set("pids_limit", limit.into());
+    }
+
+    compose_options(&mut set, options);
+
+    let service_name = spec.name.clone().unwrap_or_else(|| "app".to_string());
+    let mut file = Mapping::new();
+    file.insert(
+        "services".into(),
+        Value::Mapping(Mapping::from_iter([(
+            service_name.into(),
+            Value::Mapping(service),
+        )])),
+    );
+    // The networks already exist outside of any compose project
+    if !external_networks.is_empty() {
+        file.insert(
+            "networks".into(),
+            Value::Mapping(
+                external_networks
+                    .into_iter()
+                    .map(|network| {
+                        let external = Mapping::from_iter([("external".into(), true.into())]);
+                        (network.into(), Value::Mapping(external))
+                    })
+                    .collect(),
+            ),
+        );
+    }
+
+    let yaml = serde_yaml::to_string(&file).map_err(|e| DockerError::OperationFailed {
+        message: format!("Failed to write compose file: {e}"),
+    })?;
+
+    let mut comments: Vec<String> = unexported_comment(unexported).into_iter().collect();
+    if options.auto_remove {
+        comments.push("# --rm has no compose equivalent".to_string());
+    }
+    comments.push(yaml);
+    Ok(comments.join("\n"))
+}
+
+fn compose_options(set: &mut impl FnMut(&str, Value), options: &ContainerRuntimeOptions) {
+    if let Some(hostname) = &options.hostname {
+        set("hostname", hostname.as_str().into());
+    }
+    if let Some(domainname) = &options.domainname {
+        set("domainname", domainname.as_str().into());
+    }
+    for (enabled, key) in [
+        (options.privileged, "privileged"),
+        (options.read_only, "read_only"),
+        (options.init, "init"),
+        (options.tty, "tty"),
+        (options.interactive, "stdin_open"),
+    ] {
+        if enabled {
+            set(key, true.into());
+        }
+    }
+    for (key, values) in [
+        ("cap_add", &options.cap_add),
+        ("cap_drop", &options.cap_drop),
+        ("devices", &options.devices),
+        ("extra_hosts", &options.extra_hosts),
+        ("dns", &options.dns),
+        ("dns_search", &options.dns_search),
+        ("dns_opt", &options.dns_options),
+        ("security_opt", &options.security_opt),
+        ("group_add", &options.group_add),
+    ] {
+        if !values.is_empty() {
+            set(key, strings(values));
+        }
+    }
+    if !options.sysctls.is_empty() {
+        set("sysctls", string_map(options.sysctls.iter()));
+    }
+    if !options.ulimits.is_empty() {
+        let ulimits = options
+            .ulimits
+            .iter()
+            .map(|ulimit| {
+                let limit = if ulimit.soft == ulimit.hard {
+                    ulimit.soft.into()
+                } else {
+                    Value::Mapping(Mapping::from_iter([
+                        ("soft".into(), ulimit.soft.into()),
+                        ("hard".into(), ulimit.hard.into()),
+                    ]))
+                };
+                (ulimit.name.as_str().into(), limit)
+            })
+            .collect();
+        set("ulimits", Value::Mapping(ulimits));
+    }
+    if let Some(size) = options.shm_size {
+        set("shm_size", size.into());
+    }
+    if let Some(mode) = &options.ipc_mode {
+        set("ipc", mode.as_str().into());
+    }
+    if let Some(mode) = &options.pid_mode {
+        set("pid", mode.as_str().into());
+    }
+    if options.log_driver.is_some() || !options.log_options.is_empty() {
+        let mut logging = Mapping::new();
+        if let Some(driver) = &options.log_driver {
+            logging.insert("driver".into(), driver.as_str().into());
+        }
+        if !options.log_options.is_empty() {
+            logging.insert("options".into(), string_map(options.log_options.iter()));
+        }
+        set("logging", Value::Mapping(logging));
+    }
+    if let Some(healthcheck) = &options.healthcheck {
+        let mut settings = Mapping::new();
+        if healthcheck.test.first().is_some_and(|kind| kind == "NONE") {
+            settings.insert("disable".into(), true.into());
+        } else {
+            settings.insert("test".into(), strings(&healthcheck.test));
+            for (key, duration) in [
+                ("interval", healthcheck.interval),
+                ("timeout", healthcheck.timeout),
+                ("start_period", healthcheck.start_period),
+                ("start_interval", healthcheck.start_interval),
+            ] {
+                if let Some(nanos) = duration {
+                    settings.insert(key.into(), duration_string(nanos).into());
+                }
+            }
+            if let Some(retries) = healthcheck.retries {
+                settings.insert("retries".into(), retries.into());
+            }
+        }
+        set("healthcheck", Value::Mapping(settings));
+    }
+    if let Some(signal) = &options.stop_signal {
+        set("stop_signal", signal.as_str().into());
+    }
+    if let Some(timeout) = options.stop_timeout {
+        set("stop_grace_period", format!("{timeout}s").into());
+    }
+    if let Some(runtime) = &options.runtime {
+        set("runtime", runtime.as_str().into());
+    }
+    if let Some(bytes) = options.memory_reservation {
+        set("mem_reservation", bytes.into());
+    }
+}
+
+fn strings(values: &[String]) -> Value {
+    Value::Sequence(values.iter().map(|v| v.as_str().into()).collect())
+}
+
+// A mapping sorted by key, so the output is stable
+fn string_map<'a>(entries: impl Iterator<Item = (&'a String, &'a String)>) -> Value {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort();
+    Value::Mapping(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.as_str().into(), value.as_str().into()))
+            .collect(),
+    )
+}
+
+fn unexported_comment(unexported: &[String]) -> Option<String> {
+    (!unexported.is_empty()).then(|| format!("# Not exported: {}", unexported.join(", ")))
+}
+
+// `[ip:][host_port:]container_port/protocol`, as `-p` and compose take it
+fn port_string(port: &PortMapping) -> String {
+    let mut value = String::new();
+    if let Some(ip) = &port.host_ip {
+        value.push_str(&format!("{ip}:"));
+        if port.host_port.is_none() {
+            value.push(':');
+        }
+    }
+    if let Some(host_port) = port.host_port {
+        value.push_str(&format!("{host_port}:"));
+    }
+    value.push_str(&port.container_port.to_string());
+    if port.protocol != "tcp" {
+        value.push_str(&format!("/{}", port.protocol));
+    }
+    value
+}
+
+// `[source:]target[:ro]`, as `-v` and compose take it
+fn volume_string(mount: &MountSpec) -> String {
+    let mut value = match &mount.source {
+        Some(source) => format!("{source}:{}", mount.target),
+        None => mount.target.clone(),
+    };
+    if mount.read_only {
+        value.push_str(":ro");
+    }
+    value
+}
+
+// `target[:options]`, as `--tmpfs` and compose take it
+fn tmpfs_string(target: &str, options: &str) -> String {
+    if options.is_empty() {
+        target.to_string()
+    } else {
+        format!("{target}:{options}")
+    }
+}
+
+// `host_path[:container_path[:permissions]]`, leaving out what matches the defaults
+fn device_string(device: &DeviceMapping) -> Option<String> {
+    let host_path = device
+        .path_on_host
+        .clone()
+        .filter(|path| !path.is_empty())?;
+    let container_path = device
+        .path_in_container
+        .clone()
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| host_path.clone());
+    let permissions = device
+        .cgroup_permissions
+        .clone()
+        .filter(|permissions| !permissions.is_empty() && permissions != "rwm");
+
+    Some(match permissions {
+        Some(permissions) => format!("{host_path}:{container_path}:{permissions}"),
+        None if container_path != host_path => format!("{host_path}:{container_path}"),
+        None => host_path,
+    })
+}
+
+// `name=soft[:hard]`, as `--ulimit` takes it
+fn ulimit_string(ulimit: &UlimitSpec) -> String {
+    if ulimit.soft == ulimit.hard {
+        format!("{}={}", ulimit.name, ulimit.soft)
+    } else {
+        format!("{}={}:{}", ulimit.name, ulimit.soft, ulimit.hard)
+    }
+}
+
+// A Go duration in the largest unit that keeps it exact
+fn duration_string(nanos: i64) -> String {
+    match nanos {
+        n if n % 1_000_000_000 == 0 => format!("{}s", n / 1_000_000_000),
+        n if n % 1_000_000 == 0 => format!("{}ms", n / 1_000_000),
+        n if n % 1_000 == 0 => format!("{}us", n / 1_000),
+        n => format!("{n}ns"),
+    }
+}
+
+fn cpus_string(nano_cpus: i64) -> String {
+    let cpus = format!("{:.3}", nano_cpus as f64 / 1e9);
+    cpus.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// Anonymous volumes are named by a random 64 character hex id
+fn is_anonymous_volume(name: Option<&str>) -> bool {
+    name.is_some_and(|name| name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Quotes a shell word unless it is made of characters that are always safe
+fn quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=@,+%".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::{
+        ContainerConfig, EndpointSettings, HostConfig, HostConfigLogConfig, NetworkSettings,
+        PortBinding, RestartPolicy, RestartPolicyNameEnum,
+    };
+    use std::collections::HashMap;
+
+    const ID: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn port(
+        container_port: u16,
+        protocol: &str,
+        ip: Option<&str>,
+        host: Option<u16>,
+    ) -> PortMapping {
+        PortMapping {
+            container_port,
+            protocol: protocol.to_string(),
+            host_ip: ip.map(str::to_string),
+            host_port: host,
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn image() -> ImageConfig {
+        ImageConfig {
+            env: Some(strings(&["PATH=/usr/bin", "LANG=C.UTF-8"])),
+            cmd: Some(strings(&["nginx", "-g", "daemon off;"])),
+            entrypoint: Some(strings(&["/docker-entrypoint.sh"])),
+            labels: Some(HashMap::from([("maintainer".into(), "team".into())])),
+            working_dir: Some("/srv".into()),
+            stop_signal: Some("SIGQUIT".into()),
+            volumes: Some(HashMap::from([("/cache".into(), HashMap::new())])),
+            ..Default::default()
+        }
+    }
+
+    // A container that only differs from `image()` where a test says so
+    fn container(config: ContainerConfig, host_config: HostConfig) -> ContainerInspectResponse {
+        let image = image();
+        ContainerInspectResponse {
+            id: Some(ID.into()),
+            name: Some("/web".into()),
+            config: Some(ContainerConfig {
+                image: Some("nginx:1.27".into()),
+                hostname: config.hostname.or(Some(ID[..12].into())),
+                env: config.env.or(image.env),
+                cmd: config.cmd.or(image.cmd),
+                entrypoint: config.entrypoint.or(image.entrypoint),
+                labels: config.labels.or(image.labels),
+                working_dir: config.working_dir.or(image.working_dir),
+                stop_signal: config.stop_signal.or(image.stop_signal),
+                ..config
+            }),
+            host_config: Some(HostConfig {
+                network_mode: host_config.network_mode.or(Some("bridge".into())),
+                restart_policy: host_config.restart_policy.or(Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::NO),
+                    maximum_retry_count: Some(0),
+                })),
+                shm_size: host_config.shm_size.or(Some(DEFAULT_SHM_SIZE)),
+                ipc_mode: host_config.ipc_mode.or(Some("private".into())),
+                log_config: host_config.log_config.or(Some(HostConfigLogConfig {
+                    typ: Some(DEFAULT_LOG_DRIVER.into()),
+                    config: Some(HashMap::new()),
+                })),
+                ..host_config
+            }),
+            network_settings: Some(NetworkSettings {
+                networks: Some(HashMap::from([(
+                    "bridge".into(),
+                    EndpointSettings::default(),
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn quote_words() {
+        for (word, quoted) in [
+            ("nginx", "nginx"),
+            ("KEY=value", "KEY=value"),
+            ("/etc/app:ro", "/etc/app:ro"),
+            ("", "''"),
+            ("daemon off;", "'daemon off;'"),
+            ("$HOME", "'$HOME'"),
+            ("it's", r"'it'\''s'"),
+        ] {
+            assert_eq!(quote(word), quoted, "{word}");
+        }
+    }
+
+    #[test]
+    fn port_strings() {
+        for (mapping, expected) in [
+            (port(80, "tcp", None, Some(8080)), "8080:80"),
+            (port(80, "tcp", None, None), "80"),
+            (port(53, "udp", None, Some(53)), "53:53/udp"),
+            (
+                port(80, "tcp", Some("127.0.0.1"), Some(8080)),
+                "127.0.0.1:8080:80",
+            ),
+            (port(80, "tcp", Some("127.0.0.1"), None), "127.0.0.1::80"),
+        ] {
+            assert_eq!(port_string(&mapping), expected);
+        }
+    }
+
+    #[test]
+    fn volume_strings() {
+        let mount = |source: Option<&str>, read_only| MountSpec {
+            r#type: "bind".into(),
+            source: source.map(str::to_string),
+            target: "/data".into(),
+            read_only,
+        };
+        for (mapping, expected) in [
+            (mount(Some("/srv/data"), false), "/srv/data:/data"),
+            (mount(Some("data"), true), "data:/data:ro"),
+            (mount(None, false), "/data"),
+        ] {
+            assert_eq!(volume_string(&mapping), expected);
+        }
+    }
+
+    #[test]
+    fn cpus_strings() {
+        for (nano_cpus, expected) in [
+            (1_000_000_000, "1"),
+            (1_500_000_000, "1.5"),
+            (250_000_000, "0.25"),
+            (2_000_000, "0.002"),
+        ] {
+            assert_eq!(cpus_string(nano_cpus), expected);
+        }
+    }
+
+    #[test]
+    fn duration_strings() {
+        for (nanos, expected) in [
+            (30_000_000_000, "30s"),
+            (1_500_000_000, "1500ms"),
+            (2_000, "2us"),
+            (15, "15ns"),
+        ] {
+            assert_eq!(duration_string(nanos), expected);
+        }
+    }
+
+    #[test]
+    fn device_strings() {
+        let device = |host: &str, container: &str, permissions: &str| DeviceMapping {
+            path_on_host: Some(host.into()),
+            path_in_container: Some(container.into()),
+            cgroup_permissions: Some(permissions.into()),
+        };
+        for (mapping, expected) in [
+            (device("/dev/fuse", "/dev/fuse", "rwm"), "/dev/fuse"),
+            (device("/dev/sda", "/dev/xvda", "rwm"), "/dev/sda:/dev/xvda"),
+            (device("/dev/sda", "/dev/sda", "r"), "/dev/sda:/dev/sda:r"),
+        ] {
+            assert_eq!(device_string(&mapping).as_deref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn anonymous_volumes() {
+        assert!(is_anonymous_volume(Some(ID)));
+        assert!(!is_anonymous_volume(Some("pgdata")));
+        assert!(!is_anonymous_volume(Some(&ID[1..])));
+        assert!(!is_anonymous_volume(None));
+    }
+
+    #[test]
+    fn image_defaults_are_left_out() {
+        let mut env = image().env.unwrap();
+        env.push("MODE=prod".into());
+        let labels = HashMap::from([
+            ("maintainer".into(), "team".into()),
+            ("tier".into(), "web".into()),
+            ("com.docker.compose.project".into(), "old".into()),
+        ]);
+        let memory = 512 * 1024 * 1024;
+        let inspect = container(
+            ContainerConfig {
+                env: Some(env),
+                labels: Some(labels),
+                ..Default::default()
+            },
+            HostConfig {
+                memory: Some(memory),
+                memory_swap: Some(memory * 2),
+                port_bindings: Some(HashMap::from([(
+                    "80/tcp".into(),
+                    Some(vec![PortBinding {
+                        host_ip: Some(String::new()),
+                        host_port: Some("8080".into()),
+                    }]),
+                )])),
+                ..Default::default()
+            },
+        );
+
+        let spec = spec_from_inspect(&inspect, &image());
+        assert_eq!(spec.image, "nginx:1.27");
+        assert_eq!(spec.name.as_deref(), Some("web"));
+        assert_eq!(spec.env, strings(&["MODE=prod"]));
+        assert_eq!(
+            spec.labels,
+            HashMap::from([("tier".to_string(), "web".to_string())])
+        );
+        assert_eq!(spec.command, None);
+        assert_eq!(spec.entrypoint, None);
+        assert_eq!(spec.working_dir, None);
+        assert_eq!(spec.restart_policy, None);
+        assert!(spec.networks.is_empty());
+        assert_eq!(spec.ports, vec![port(80, "tcp", None, Some(8080))]);
+        assert_eq!(spec.resources.memory, Some(memory));
+        assert_eq!(spec.resources.memory_swap, None);
+
+        let options = options_from_inspect(&inspect, &image(), DEFAULT_LOG_DRIVER);
+        assert_eq!(options, ContainerRuntimeOptions::default());
+        assert!(unexported_settings(&inspect).is_empty());
+    }
+
+    #[test]
+    fn entrypoint_override_keeps_the_command() {
+        let inspect = container(
+            ContainerConfig {
+                entrypoint: Some(strings(&["/bin/sh", "-c"])),
+                ..Default::default()
+            },
+            HostConfig::default(),
+        );
+
+        let spec = spec_from_inspect(&inspect, &image());
+        assert_eq!(spec.entrypoint, Some(strings(&["/bin/sh", "-c"])));
+        assert_eq!(spec.command, image().cmd);
+
+        let run = run_command(&spec, &ContainerRuntimeOptions::default(), &[]);
+        assert_eq!(
+            run,
+            "docker run -d --name web --entrypoint /bin/sh nginx:1.27 -c nginx -g 'daemon off;'"
+        );
+    }
+
+    #[test]
+    fn host_settings_are_exported() {
+        let inspect = container(
+            ContainerConfig {
+                hostname: Some("api".into()),
+                stop_signal: Some("SIGTERM".into()),
+                ..Default::default()
+            },
+            HostConfig {
+                privileged: Some(true),
+                cap_add: Some(strings(&["NET_ADMIN"])),
+                extra_hosts: Some(strings(&["db:10.0.0.5"])),
+                tmpfs: Some(HashMap::from([("/run".into(), "size=64m".into())])),
+                shm_size: Some(256 * 1024 * 1024),
+                log_config: Some(HostConfigLogConfig {
+                    typ: Some("local".into()),
+                    config: Some(HashMap::from([("max-size".into(), "10m".into())])),
+                }),
+                links: Some(strings(&["/db:/web/db"])),
+                ..Default::default()
+            },
+        );
+
+        let options = options_from_inspect(&inspect, &image(), DEFAULT_LOG_DRIVER);
+        assert_eq!(options.hostname.as_deref(), Some("api"));
+        assert_eq!(options.stop_signal.as_deref(), Some("SIGTERM"));
+        assert!(options.privileged);
+        assert_eq!(options.shm_size, Some(256 * 1024 * 1024));
+        assert_eq!(options.log_driver.as_deref(), Some("local"));
+        let unexported = unexported_settings(&inspect);
+        assert_eq!(unexported, strings(&["--link"]));
+
+        let spec = spec_from_inspect(&inspect, &image());
+        let run = run_command(&spec, &options, &unexported);
+        assert_eq!(
+            run,
+            "# Not exported: --link\n\
+             docker run -d --name web --tmpfs /run:size=64m --hostname api --privileged \
+             --cap-add NET_ADMIN --add-host db:10.0.0.5 --shm-size 268435456 \
+             --log-driver local --log-opt max-size=10m --stop-signal SIGTERM nginx:1.27"
+        );
+
+        let compose = compose_service(&spec, &options, &unexported).unwrap();
+        assert!(compose.starts_with("# Not exported: --link\n"));
+        let file: Value = serde_yaml::from_str(&compose).unwrap();
+        let service = &file["services"]["web"];
+        assert_eq!(service["hostname"], Value::from("api"));
+        assert_eq!(service["privileged"], Value::from(true));
+        assert_eq!(service["tmpfs"][0], Value::from("/run:size=64m"));
+        assert_eq!(service["logging"]["driver"], Value::from("local"));
+        assert_eq!(
+            service["logging"]["options"]["max-size"],
+            Value::from("10m")
+        );
+    }
+}
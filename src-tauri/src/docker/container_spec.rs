@@ -0,0 +1,338 @@
+use crate::utils::{DockerError, Result};
+use bollard::models::{
+    ContainerConfig, ContainerCreateBody, EndpointIpamConfig, EndpointSettings, HostConfig,
+    ImageConfig, Mount, MountTypeEnum, NetworkingConfig, PortBinding, PortMap, RestartPolicy,
+    RestartPolicyNameEnum,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Everything needed to create a container, as entered in the create dialog.
+/// Unset fields fall back to the image's or the daemon's defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerSpec {
+    pub image: String,
+    pub name: Option<String>,
+    pub command: Option<Vec<String>>,    // replaces the image's CMD
+    pub entrypoint: Option<Vec<String>>, // replaces the image's ENTRYPOINT
+    #[serde(default)]
+    pub env: Vec<String>, // KEY=value
+    #[serde(default)]
+    pub ports: Vec<PortMapping>,
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+    #[serde(default)]
+    pub networks: Vec<NetworkAttachment>,
+    pub restart_policy: Option<RestartPolicySpec>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub resources: ContainerResources,
+    pub user: Option<String>,
+    pub working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortMapping {
+    pub container_port: u16,
+    #[serde(default = "default_protocol")]
+    pub protocol: String, // "tcp", "udp" or "sctp"
+    pub host_ip: Option<String>,
+    pub host_port: Option<u16>, // unset publishes on a random port
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MountSpec {
+    pub r#type: String,         // "bind", "volume" or "tmpfs"
+    pub source: Option<String>, // host path or volume name; unset for tmpfs
+    pub target: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkAttachment {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub ipv4_address: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestartPolicySpec {
+    pub name: String, // "no", "always", "unless-stopped" or "on-failure"
+    pub maximum_retry_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerResources {
+    pub memory: Option<i64>,      // bytes
+    pub memory_swap: Option<i64>, // memory plus swap in bytes, -1 for unlimited
+    pub cpu_shares: Option<i64>,
+    pub nano_cpus: Option<i64>, // CPUs in units of 10^-9
+    pub cpuset_cpus: Option<String>,
+    pub pids_limit: Option<i64>,
+}
+
+/// Changes to make when recreating a container. Unset fields keep the
+/// container's current value; lists and maps replace it as a whole.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerPatch {
+    pub image: Option<String>,
+    pub command: Option<Vec<String>>,
+    pub entrypoint: Option<Vec<String>>,
+    pub env: Option<Vec<String>>,
+    pub ports: Option<Vec<PortMapping>>,
+    pub labels: Option<HashMap<String, String>>,
+    pub restart_policy: Option<RestartPolicySpec>,
+    #[serde(default)]
+    pub resources: ContainerResources,
+    pub user: Option<String>,
+    pub working_dir: Option<String>,
+}
+
+fn default_protocol() -> String {
+    "tcp".to_string()
+}
+
+impl ContainerSpec {
+    /// Maps the spec onto the engine's create body. Only the first network is
+    /// attached at creation; the daemon takes the others through a connect
+    /// call each, see `endpoint_settings`.
+    pub fn to_config(&self) -> Result<ContainerCreateBody> {
+        if self.image.trim().is_empty() {
+            return Err(DockerError::InvalidInput {
+                message: "Image is required".to_string(),
+            });
+        }
+
+        let (exposed_ports, port_bindings) = port_map(&self.ports);
+
+        let mounts = self
+            .mounts
+            .iter()
+            .map(|mount| {
+                let typ = mount.r#type.parse::<MountTypeEnum>().map_err(|_| {
+                    DockerError::InvalidInput {
+                        message: format!("Unknown mount type '{}'", mount.r#type),
+                    }
+                })?;
+                Ok(Mount {
+                    target: Some(mount.target.clone()),
+                    source: mount.source.clone().filter(|source| !source.is_empty()),
+                    typ: Some(typ),
+                    read_only: Some(mount.read_only),
+                    ..Default::default()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let restart_policy = self
+            .restart_policy
+            .as_ref()
+            .map(RestartPolicySpec::to_restart_policy)
+            .transpose()?;
+
+        let mut host_config = HostConfig {
+            port_bindings: (!port_bindings.is_empty()).then_some(port_bindings),
+            mounts: (!mounts.is_empty()).then_some(mounts),
+            restart_policy,
+            network_mode: self.networks.first().map(|network| network.name.clone()),
+            ..Default::default()
+        };
+        self.resources.apply_to(&mut host_config);
+
+        let networking_config = self.networks.first().map(|network| NetworkingConfig {
+            endpoints_config: Some(HashMap::from([(
+                network.name.clone(),
+                endpoint_settings(network),
+            )])),
+        });
+
+        Ok(ContainerCreateBody {
+            image: Some(self.image.clone()),
+            cmd: self.command.clone(),
+            entrypoint: self.entrypoint.clone(),
+            env: (!self.env.is_empty()).then(|| self.env.clone()),
+            exposed_ports: (!exposed_ports.is_empty()).then_some(exposed_ports),
+            labels: (!self.labels.is_empty()).then(|| self.labels.clone()),
+            user: self.user.clone().filter(|user| !user.is_empty()),
+            working_dir: self.working_dir.clone().filter(|dir| !dir.is_empty()),
+            host_config: Some(host_config),
+            networking_config,
+            ..Default::default()
+        })
+    }
+}
+
+impl RestartPolicySpec {
+    pub fn to_restart_policy(&self) -> Result<RestartPolicy> {
+        let name =
+            self.name
+                .parse::<RestartPolicyNameEnum>()
+                .map_err(|_| DockerError::InvalidInput {
+                    message: format!("Unknown restart policy '{}'", self.name),
+                })?;
+        Ok(RestartPolicy {
+            name: Some(name),
+            maximum_retry_count: self.maximum_retry_count,
+        })
+    }
+}
+
+impl ContainerResources {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Overrides the limits that are set, keeping the others
+    pub fn apply_to(&self, host_config: &mut HostConfig) {
+        if self.memory.is_some() {
+            host_config.memory = self.memory;
+        }
+        if self.memory_swap.is_some() {
+            host_config.memory_swap = self.memory_swap;
+        }
+        if self.cpu_shares.is_some() {
+            host_config.cpu_shares = self.cpu_shares;
+        }
+        if self.nano_cpus.is_some() {
+            host_config.nano_cpus = self.nano_cpus;
+        }
+        if self.cpuset_cpus.is_some() {
+            host_config.cpuset_cpus = self.cpuset_cpus.clone();
+        }
+        if self.pids_limit.is_some() {
+            host_config.pids_limit = self.pids_limit;
+        }
+    }
+}
+
+impl ContainerPatch {
+    /// Applies the patch to a container's create body and host config
+    pub fn apply(
+        &self,
+        config: &mut ContainerCreateBody,
+        host_config: &mut HostConfig,
+    ) -> Result<()> {
+        if let Some(image) = self.image.clone().filter(|image| !image.trim().is_empty()) {
+            config.image = Some(image);
+        }
+        if let Some(command) = &self.command {
+            config.cmd = Some(command.clone());
+        }
+        if let Some(entrypoint) = &self.entrypoint {
+            config.entrypoint = Some(entrypoint.clone());
+        }
+        if let Some(env) = &self.env {
+            config.env = Some(env.clone());
+        }
+        if let Some(labels) = &self.labels {
+            config.labels = Some(labels.clone());
+        }
+        if let Some(user) = &self.user {
+            config.user = Some(user.clone());
+        }
+        if let Some(working_dir) = &self.working_dir {
+            config.working_dir = Some(working_dir.clone());
+        }
+        if let Some(ports) = &self.ports {
+            let (exposed_ports, port_bindings) = port_map(ports);
+            config.exposed_ports = Some(exposed_ports);
+            host_config.port_bindings = Some(port_bindings);
+        }
+        if let Some(restart_policy) = &self.restart_policy {
+            host_config.restart_policy = Some(restart_policy.to_restart_policy()?);
+        }
+        self.resources.apply_to(host_config);
+        Ok(())
+    }
+}
+
+/// Exposed ports and host bindings for a list of port mappings
+fn port_map(ports: &[PortMapping]) -> (HashMap<String, HashMap<(), ()>>, PortMap) {
+    let mut exposed_ports = HashMap::new();
+    let mut port_bindings: PortMap = HashMap::new();
+    for port in ports {
+        let key = format!("{}/{}", port.container_port, port.protocol);
+        exposed_ports.insert(key.clone(), HashMap::new());
+        port_bindings
+            .entry(key)
+            .or_default()
+            .get_or_insert_with(Vec::new)
+            .push(PortBinding {
+                host_ip: port.host_ip.clone().filter(|ip| !ip.is_empty()),
+                host_port: Some(port.host_port.map(|p| p.to_string()).unwrap_or_default()),
+            });
+    }
+    (exposed_ports, port_bindings)
+}
+
+/// The endpoint a container gets on an attached network
+pub fn endpoint_settings(network: &NetworkAttachment) -> EndpointSettings {
+    EndpointSettings {
+        aliases: (!network.aliases.is_empty()).then(|| network.aliases.clone()),
+        ipam_config: network
+            .ipv4_address
+            .clone()
+            .filter(|address| !address.is_empty())
+            .map(|address| EndpointIpamConfig {
+                ipv4_address: Some(address),
+                ..Default::default()
+            }),
+        ..Default::default()
+    }
+}
+
+/// A create body carrying over an inspected container's configuration. The
+/// host and networking configs come from elsewhere in the inspect output.
+pub fn create_body(config: ContainerConfig) -> ContainerCreateBody {
+    ContainerCreateBody {
+        hostname: config.hostname,
+        domainname: config.domainname,
+        user: config.user,
+        attach_stdin: config.attach_stdin,
+        attach_stdout: config.attach_stdout,
+        attach_stderr: config.attach_stderr,
+        exposed_ports: config.exposed_ports,
+        tty: config.tty,
+        open_stdin: config.open_stdin,
+        stdin_once: config.stdin_once,
+        env: config.env,
+        cmd: config.cmd,
+        healthcheck: config.healthcheck,
+        args_escaped: config.args_escaped,
+        image: config.image,
+        volumes: config.volumes,
+        working_dir: config.working_dir,
+        entrypoint: config.entrypoint,
+        network_disabled: config.network_disabled,
+        mac_address: config.mac_address,
+        on_build: config.on_build,
+        labels: config.labels,
+        stop_signal: config.stop_signal,
+        stop_timeout: config.stop_timeout,
+        shell: config.shell,
+        host_config: None,
+        networking_config: None,
+    }
+}
+
+/// Drops the entrypoint, command, environment and labels a container only
+/// inherited from its image, so that a new image brings its own defaults.
+/// As in `container_export::spec_from_inspect`, an explicit entrypoint keeps
+/// the command, since setting one resets the image's CMD.
+pub fn strip_image_defaults(config: &mut ContainerCreateBody, image: &ImageConfig) {
+    if config.entrypoint.is_some() && config.entrypoint == image.entrypoint {
+        config.entrypoint = None;
+    }
+    if config.entrypoint.is_none() && config.cmd.is_some() && config.cmd == image.cmd {
+        config.cmd = None;
+    }
+    if let (Some(env), Some(image_env)) = (config.env.as_mut(), image.env.as_ref()) {
+        env.retain(|var| !image_env.contains(var));
+    }
+    if let (Some(labels), Some(image_labels)) = (config.labels.as_mut(), image.labels.as_ref()) {
+        labels.retain(|key, value| image_labels.get(key) != Some(value));
+    }
+}
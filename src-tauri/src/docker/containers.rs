@@ -1,16 +1,29 @@
 use crate::docker::client::DOCKER_CLIENT;
-use crate::utils::{log_docker_operation, DockerError, Result};
-use bollard::container::Stats;
-use bollard::container::{
-    InspectContainerOptions, KillContainerOptions, ListContainersOptions, LogsOptions,
-    RemoveContainerOptions, RestartContainerOptions, StartContainerOptions, StatsOptions,
-    StopContainerOptions,
+use crate::docker::container_spec::{
+    create_body, endpoint_settings, strip_image_defaults, ContainerPatch, ContainerResources,
+    ContainerSpec, RestartPolicySpec,
 };
+use crate::docker::images::pull_image;
+use crate::docker::registry::normalize_image_reference;
+use crate::docker::subscriptions::SUBSCRIPTIONS;
+use crate::utils::{log_docker_operation, parse_timestamp, DockerError, Result};
+use bollard::container::LogOutput;
 use bollard::exec::{CreateExecOptions, StartExecResults};
-use bollard::models::{ContainerInspectResponse, ContainerSummary};
-use futures_util::stream::TryStreamExt;
+use bollard::models::{
+    ContainerInspectResponse, ContainerStatsResponse, ContainerSummary, ContainerUpdateBody,
+    EndpointSettings, Mount, MountPointTypeEnum, MountTypeEnum, NetworkConnectRequest,
+    NetworkingConfig,
+};
+use bollard::query_parameters::{
+    CreateContainerOptions, InspectContainerOptions, KillContainerOptions, ListContainersOptions,
+    LogsOptions, RemoveContainerOptions, RenameContainerOptions, RestartContainerOptions,
+    StartContainerOptions, StatsOptions, StopContainerOptions,
+};
+use futures_util::stream::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerListItem {
@@ -99,6 +112,19 @@ pub struct ContainerDetails {
     pub network_settings: serde_json::Value,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerUpdateResult {
+    pub warnings: Vec<String>,
+    pub host_config: serde_json::Value, // as inspected after the update
+}
+
+/// Outcome of one container in a bulk action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerActionResult {
+    pub id: String,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerState {
     pub status: String,
@@ -139,14 +165,14 @@ pub struct ContainerStatsData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerLogLine {
     pub timestamp: Option<String>,
-    pub stream: String, // "stdout" or "stderr"
+    pub stream: String, // "stdout", "stderr", "stdin" or "console"
     pub content: String,
 }
 
-pub async fn list_containers(all: bool) -> Result<Vec<ContainerListItem>> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn list_containers(host: Option<&str>, all: bool) -> Result<Vec<ContainerListItem>> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
-    let options = Some(ListContainersOptions::<String> {
+    let options = Some(ListContainersOptions {
         all,
         ..Default::default()
     });
@@ -173,12 +199,13 @@ pub async fn list_containers(all: bool) -> Result<Vec<ContainerListItem>> {
 }
 
 pub async fn exec_container(
+    host: Option<&str>,
     id: &str,
     cmd: Vec<String>,
     interactive: bool,
     tty: bool,
 ) -> Result<String> {
-    let client = DOCKER_CLIENT.get_client().await?;
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     // Create exec instance
     let exec_options = CreateExecOptions {
@@ -238,8 +265,8 @@ pub async fn exec_container(
     }
 }
 
-pub async fn inspect_container(id: &str) -> Result<ContainerDetails> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn inspect_container(host: Option<&str>, id: &str) -> Result<ContainerDetails> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = Some(InspectContainerOptions { size: true });
 
@@ -264,11 +291,88 @@ pub async fn inspect_container(id: &str) -> Result<ContainerDetails> {
     }
 }
 
-pub async fn start_container(id: &str) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+/// Creates a container from `spec`, optionally pulling its image first and
+/// starting it afterwards, and returns the new container's details.
+pub async fn create_container(
+    host: Option<&str>,
+    spec: &ContainerSpec,
+    pull: bool,
+    start: bool,
+) -> Result<ContainerDetails> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let config = spec.to_config()?;
+    if pull {
+        pull_image(host, &normalize_image_reference(&spec.image), None).await?;
+    }
+
+    let options = spec
+        .name
+        .clone()
+        .filter(|name| !name.is_empty())
+        .map(|name| CreateContainerOptions {
+            name: Some(name),
+            ..Default::default()
+        });
+
+    let response = match client.create_container(options, config).await {
+        Ok(response) => response,
+        Err(e) => {
+            log_docker_operation("create_container", false, Some(&e.to_string()));
+            return if e.to_string().contains("404") {
+                Err(DockerError::ImageNotFound {
+                    id: spec.image.clone(),
+                })
+            } else {
+                Err(DockerError::Connection(e))
+            };
+        }
+    };
+    let id = response.id;
+    for warning in response.warnings {
+        warn!("Creating container {}: {}", id, warning);
+    }
+
+    // Networks past the first can only be attached once the container exists
+    for network in spec.networks.iter().skip(1) {
+        let options = NetworkConnectRequest {
+            container: Some(id.clone()),
+            endpoint_config: Some(endpoint_settings(network)),
+        };
+        if let Err(e) = client.connect_network(&network.name, options).await {
+            log_docker_operation("create_container", false, Some(&e.to_string()));
+            let _ = remove_container(host, &id, true, false).await;
+            return if e.to_string().contains("404") {
+                Err(DockerError::NetworkNotFound {
+                    id: network.name.clone(),
+                })
+            } else {
+                Err(DockerError::Connection(e))
+            };
+        }
+    }
+
+    log_docker_operation(
+        "create_container",
+        true,
+        Some(&format!("Created container {id} from {}", spec.image)),
+    );
+
+    // A container that was asked to run but can't is not left behind
+    if start {
+        if let Err(e) = start_container(host, &id).await {
+            let _ = remove_container(host, &id, true, false).await;
+            return Err(e);
+        }
+    }
+    inspect_container(host, &id).await
+}
+
+pub async fn start_container(host: Option<&str>, id: &str) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     match client
-        .start_container(id, None::<StartContainerOptions<String>>)
+        .start_container(id, None::<StartContainerOptions>)
         .await
     {
         Ok(_) => {
@@ -290,11 +394,12 @@ pub async fn start_container(id: &str) -> Result<()> {
     }
 }
 
-pub async fn stop_container(id: &str, timeout: Option<i64>) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn stop_container(host: Option<&str>, id: &str, timeout: Option<i64>) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = StopContainerOptions {
-        t: timeout.unwrap_or(10), // Remove the `as isize` conversion
+        t: Some(timeout.unwrap_or(10) as i32),
+        ..Default::default()
     };
 
     match client.stop_container(id, Some(options)).await {
@@ -317,11 +422,12 @@ pub async fn stop_container(id: &str, timeout: Option<i64>) -> Result<()> {
     }
 }
 
-pub async fn restart_container(id: &str, timeout: Option<i64>) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn restart_container(host: Option<&str>, id: &str, timeout: Option<i64>) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = RestartContainerOptions {
-        t: timeout.unwrap_or(10) as isize, // Remove the `as isize` conversion here too
+        t: Some(timeout.unwrap_or(10) as i32),
+        ..Default::default()
     };
 
     match client.restart_container(id, Some(options)).await {
@@ -344,8 +450,13 @@ pub async fn restart_container(id: &str, timeout: Option<i64>) -> Result<()> {
     }
 }
 
-pub async fn remove_container(id: &str, force: bool, remove_volumes: bool) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn remove_container(
+    host: Option<&str>,
+    id: &str,
+    force: bool,
+    remove_volumes: bool,
+) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = Some(RemoveContainerOptions {
         force,
@@ -373,11 +484,11 @@ pub async fn remove_container(id: &str, force: bool, remove_volumes: bool) -> Re
     }
 }
 
-pub async fn kill_container(id: &str, signal: Option<&str>) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn kill_container(host: Option<&str>, id: &str, signal: Option<&str>) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = Some(KillContainerOptions {
-        signal: signal.unwrap_or("SIGKILL"),
+        signal: signal.unwrap_or("SIGKILL").to_string(),
     });
 
     match client.kill_container(id, options).await {
@@ -400,8 +511,447 @@ pub async fn kill_container(id: &str, signal: Option<&str>) -> Result<()> {
     }
 }
 
-pub async fn get_container_stats(id: &str) -> Result<ContainerStatsData> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn pause_container(host: Option<&str>, id: &str) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    match client.pause_container(id).await {
+        Ok(_) => {
+            log_docker_operation(
+                "pause_container",
+                true,
+                Some(&format!("Paused container {id}")),
+            );
+            Ok(())
+        }
+        Err(e) => {
+            log_docker_operation("pause_container", false, Some(&e.to_string()));
+            if e.to_string().contains("404") {
+                Err(DockerError::ContainerNotFound { id: id.to_string() })
+            } else {
+                Err(DockerError::Connection(e))
+            }
+        }
+    }
+}
+
+pub async fn unpause_container(host: Option<&str>, id: &str) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    match client.unpause_container(id).await {
+        Ok(_) => {
+            log_docker_operation(
+                "unpause_container",
+                true,
+                Some(&format!("Unpaused container {id}")),
+            );
+            Ok(())
+        }
+        Err(e) => {
+            log_docker_operation("unpause_container", false, Some(&e.to_string()));
+            if e.to_string().contains("404") {
+                Err(DockerError::ContainerNotFound { id: id.to_string() })
+            } else {
+                Err(DockerError::Connection(e))
+            }
+        }
+    }
+}
+
+/// Changes resource limits and the restart policy of a container in place,
+/// running or not. Limits left unset are kept as they are.
+pub async fn update_container_resources(
+    host: Option<&str>,
+    id: &str,
+    resources: &ContainerResources,
+    restart_policy: Option<&RestartPolicySpec>,
+) -> Result<ContainerUpdateResult> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    if resources.is_empty() && restart_policy.is_none() {
+        return Err(DockerError::InvalidInput {
+            message: "Nothing to update".to_string(),
+        });
+    }
+
+    let options = ContainerUpdateBody {
+        memory: resources.memory,
+        memory_swap: resources.memory_swap,
+        cpu_shares: resources.cpu_shares,
+        nano_cpus: resources.nano_cpus,
+        cpuset_cpus: resources.cpuset_cpus.clone(),
+        pids_limit: resources.pids_limit,
+        restart_policy: restart_policy
+            .map(RestartPolicySpec::to_restart_policy)
+            .transpose()?,
+        ..Default::default()
+    };
+
+    if let Err(e) = client.update_container(id, options).await {
+        log_docker_operation("update_container_resources", false, Some(&e.to_string()));
+        return if e.to_string().contains("404") {
+            Err(DockerError::ContainerNotFound { id: id.to_string() })
+        } else {
+            Err(DockerError::Connection(e))
+        };
+    }
+
+    let container = client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await?;
+    let host_config = container.host_config.unwrap_or_default();
+
+    // bollard drops the daemon's update response, so report limits the
+    // daemon did not apply (e.g. swap limits without kernel support) instead
+    let mut warnings = Vec::new();
+    let mut check = |name: &str, requested: Option<i64>, applied: Option<i64>| {
+        if let Some(requested) = requested {
+            if applied != Some(requested) {
+                warnings.push(format!(
+                    "{name} was set to {requested} but the container reports {}",
+                    applied.map_or_else(|| "nothing".to_string(), |v| v.to_string())
+                ));
+            }
+        }
+    };
+    check("Memory", resources.memory, host_config.memory);
+    check(
+        "Memory swap",
+        resources.memory_swap,
+        host_config.memory_swap,
+    );
+    check("CPU shares", resources.cpu_shares, host_config.cpu_shares);
+    check("Nano CPUs", resources.nano_cpus, host_config.nano_cpus);
+    check("PIDs limit", resources.pids_limit, host_config.pids_limit);
+    if let Some(cpuset) = &resources.cpuset_cpus {
+        if host_config.cpuset_cpus.as_ref() != Some(cpuset) {
+            warnings.push(format!("CPU set {cpuset} was not applied"));
+        }
+    }
+    for warning in &warnings {
+        warn!("Updating container {}: {}", id, warning);
+    }
+
+    log_docker_operation(
+        "update_container_resources",
+        true,
+        Some(&format!("Updated resources of container {id}")),
+    );
+    Ok(ContainerUpdateResult {
+        warnings,
+        host_config: serde_json::to_value(&host_config).unwrap_or_default(),
+    })
+}
+
+/// Replaces a container with one built from its current configuration plus
+/// `patch`, keeping its name, networks and mounts (anonymous volumes are
+/// carried over too). The old container is stopped and renamed aside while
+/// the new one is created; if creating or starting it fails, the new one is
+/// removed and the original restored. The new container is started only if
+/// the old one was running. Containers started with `--rm` are refused, as
+/// the daemon deletes them as soon as they stop.
+pub async fn recreate_container(
+    host: Option<&str>,
+    id: &str,
+    patch: &ContainerPatch,
+) -> Result<ContainerDetails> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let old = match client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await
+    {
+        Ok(container) => container,
+        Err(e) => {
+            log_docker_operation("recreate_container", false, Some(&e.to_string()));
+            return if e.to_string().contains("404") {
+                Err(DockerError::ContainerNotFound { id: id.to_string() })
+            } else {
+                Err(DockerError::Connection(e))
+            };
+        }
+    };
+
+    let old_id = old.id.clone().unwrap_or_default();
+    let short_id: String = old_id.chars().take(12).collect();
+    let name = old
+        .name
+        .clone()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    let was_running = old
+        .state
+        .as_ref()
+        .and_then(|state| state.running)
+        .unwrap_or_default();
+
+    let auto_remove = old
+        .host_config
+        .as_ref()
+        .and_then(|host_config| host_config.auto_remove)
+        .unwrap_or_default();
+    if auto_remove {
+        log_docker_operation(
+            "recreate_container",
+            false,
+            Some(&format!("Container {name} is removed when stopped")),
+        );
+        return Err(DockerError::InvalidInput {
+            message: format!(
+                "Container {name} was started with --rm and would be deleted when stopped"
+            ),
+        });
+    }
+
+    let mut config = create_body(old.config.clone().unwrap_or_default());
+    let mut host_config = old.host_config.clone().unwrap_or_default();
+
+    // Keep the exact image the container runs; its tag may have moved since
+    if old.image.is_some() {
+        config.image = old.image.clone();
+    }
+
+    // A new image brings its own defaults, so only carry over what was set
+    // on the container itself
+    let changes_image = patch
+        .image
+        .as_deref()
+        .is_some_and(|image| !image.trim().is_empty());
+    if changes_image {
+        let image_config = match &old.image {
+            Some(image_id) => client
+                .inspect_image(image_id)
+                .await
+                .ok()
+                .and_then(|image| image.config),
+            None => None,
+        };
+        strip_image_defaults(&mut config, &image_config.unwrap_or_default());
+    }
+
+    // The daemon defaults the hostname to the short id; let it pick a new one
+    if config.hostname.as_deref() == Some(short_id.as_str()) {
+        config.hostname = None;
+    }
+
+    // Anonymous volumes would be created afresh, so mount the old ones by name
+    let mounted_targets: Vec<String> = host_config
+        .mounts
+        .iter()
+        .flatten()
+        .filter_map(|mount| mount.target.clone())
+        .chain(
+            host_config
+                .binds
+                .iter()
+                .flatten()
+                .filter_map(|bind| bind.split(':').nth(1).map(str::to_string)),
+        )
+        .collect();
+    for mount in old.mounts.iter().flatten() {
+        let is_volume = mount.typ == Some(MountPointTypeEnum::VOLUME);
+        let Some(destination) = &mount.destination else {
+            continue;
+        };
+        if is_volume && !mounted_targets.contains(destination) {
+            host_config.mounts.get_or_insert_with(Vec::new).push(Mount {
+                target: Some(destination.clone()),
+                source: mount.name.clone(),
+                typ: Some(MountTypeEnum::VOLUME),
+                read_only: mount.rw.map(|rw| !rw),
+                ..Default::default()
+            });
+            if let Some(volumes) = config.volumes.as_mut() {
+                volumes.remove(destination);
+            }
+        }
+    }
+
+    patch.apply(&mut config, &mut host_config)?;
+
+    // The network the container was created on goes into the create call,
+    // any others are connected afterwards
+    let network_mode = match host_config.network_mode.as_deref() {
+        None | Some("default") => "bridge".to_string(),
+        Some(mode) => mode.to_string(),
+    };
+    let mut networks: Vec<(String, EndpointSettings)> = old
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.networks.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(network, endpoint)| {
+            let aliases: Vec<String> = endpoint
+                .aliases
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|alias| *alias != short_id && *alias != name)
+                .collect();
+            let settings = EndpointSettings {
+                aliases: (!aliases.is_empty()).then_some(aliases),
+                ipam_config: endpoint.ipam_config,
+                links: endpoint.links,
+                driver_opts: endpoint.driver_opts,
+                ..Default::default()
+            };
+            (network, settings)
+        })
+        .collect();
+    networks.sort_by_key(|(network, _)| *network != network_mode);
+    let primary = networks
+        .first()
+        .filter(|(network, _)| *network == network_mode)
+        .cloned();
+    let extra_networks: Vec<(String, EndpointSettings)> = networks
+        .into_iter()
+        .skip(usize::from(primary.is_some()))
+        .collect();
+    config.networking_config = primary.map(|(network, settings)| NetworkingConfig {
+        endpoints_config: Some(HashMap::from([(network, settings)])),
+    });
+    config.host_config = Some(host_config);
+
+    // Move the original aside so the new container can take its name
+    if was_running {
+        stop_container(host, &old_id, None).await?;
+    }
+    let backup_name = format!("{name}-old-{short_id}");
+    if let Err(e) = client
+        .rename_container(
+            &old_id,
+            RenameContainerOptions {
+                name: backup_name.clone(),
+            },
+        )
+        .await
+    {
+        log_docker_operation("recreate_container", false, Some(&e.to_string()));
+        if was_running {
+            let _ = start_container(host, &old_id).await;
+        }
+        return Err(DockerError::Connection(e));
+    }
+
+    let mut new_id = None;
+    let outcome: Result<()> = async {
+        let options = Some(CreateContainerOptions {
+            name: Some(name.clone()),
+            ..Default::default()
+        });
+        let response = client.create_container(options, config).await?;
+        for warning in &response.warnings {
+            warn!("Recreating container {}: {}", name, warning);
+        }
+        let id = new_id.insert(response.id);
+
+        for (network, endpoint_config) in extra_networks {
+            let options = NetworkConnectRequest {
+                container: Some(id.clone()),
+                endpoint_config: Some(endpoint_config),
+            };
+            client.connect_network(&network, options).await?;
+        }
+        if was_running {
+            client
+                .start_container(id, None::<StartContainerOptions>)
+                .await?;
+        }
+        Ok(())
+    }
+    .await;
+
+    match outcome {
+        Ok(()) => {
+            let new_id = new_id.unwrap_or_default();
+            if let Err(e) = remove_container(host, &old_id, false, false).await {
+                warn!("Removing replaced container {}: {}", backup_name, e);
+            }
+            log_docker_operation(
+                "recreate_container",
+                true,
+                Some(&format!("Recreated container {name} as {new_id}")),
+            );
+            inspect_container(host, &new_id).await
+        }
+        Err(e) => {
+            // Roll back to the original container
+            if let Some(new_id) = new_id {
+                let _ = remove_container(host, &new_id, true, false).await;
+            }
+            let restored = client
+                .rename_container(&old_id, RenameContainerOptions { name: name.clone() })
+                .await;
+            if let Err(rename_error) = restored {
+                warn!(
+                    "Restoring the name of container {}: {}",
+                    backup_name, rename_error
+                );
+            }
+            if was_running {
+                let _ = start_container(host, &old_id).await;
+            }
+            log_docker_operation(
+                "recreate_container",
+                false,
+                Some(&format!(
+                    "Recreating {name} failed, restored the original: {e}"
+                )),
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Runs one lifecycle action on several containers, one after another, with
+/// each action's default options. Failures are reported per container
+/// instead of stopping the batch.
+pub async fn bulk_container_action(
+    host: Option<&str>,
+    ids: &[String],
+    action: &str,
+) -> Result<Vec<ContainerActionResult>> {
+    if !matches!(
+        action,
+        "start" | "stop" | "restart" | "pause" | "unpause" | "kill" | "remove"
+    ) {
+        return Err(DockerError::InvalidInput {
+            message: format!("Unknown container action '{action}'"),
+        });
+    }
+
+    let mut results = Vec::with_capacity(ids.len());
+    for id in ids {
+        let outcome = match action {
+            "start" => start_container(host, id).await,
+            "stop" => stop_container(host, id, None).await,
+            "restart" => restart_container(host, id, None).await,
+            "pause" => pause_container(host, id).await,
+            "unpause" => unpause_container(host, id).await,
+            "kill" => kill_container(host, id, None).await,
+            _ => remove_container(host, id, false, false).await,
+        };
+        results.push(ContainerActionResult {
+            id: id.clone(),
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    log_docker_operation(
+        "bulk_container_action",
+        failed == 0,
+        Some(&format!(
+            "{action}: {} of {} containers succeeded",
+            results.len() - failed,
+            results.len()
+        )),
+    );
+    Ok(results)
+}
+
+pub async fn get_container_stats(host: Option<&str>, id: &str) -> Result<ContainerStatsData> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = Some(StatsOptions {
         stream: false,
@@ -437,22 +987,32 @@ pub async fn get_container_stats(id: &str) -> Result<ContainerStatsData> {
 
 #[allow(clippy::needless_update)]
 pub async fn get_container_logs(
+    host: Option<&str>,
     id: &str,
     follow: bool,
     tail: Option<String>,
     since: Option<String>,
     until: Option<String>,
 ) -> Result<Vec<ContainerLogLine>> {
-    let client = DOCKER_CLIENT.get_client().await?;
+    // A followed stream never ends, so collecting it would never return
+    if follow {
+        return Err(DockerError::InvalidInput {
+            message: "Use subscribe_container_logs to follow logs".to_string(),
+        });
+    }
+
+    let since = log_time("since", since.as_deref())?;
+    let until = log_time("until", until.as_deref())?;
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
-    let options = Some(LogsOptions::<String> {
+    let options = Some(LogsOptions {
         stdout: true,
         stderr: true,
-        follow,
+        follow: false,
         timestamps: true,
         tail: tail.unwrap_or_else(|| "100".to_string()),
-        since: since.and_then(|s| s.parse::<i64>().ok()).unwrap_or(0),
-        until: until.and_then(|s| s.parse::<i64>().ok()).unwrap_or(0),
+        since,
+        until,
         ..Default::default()
     });
 
@@ -461,26 +1021,7 @@ pub async fn get_container_logs(
             let mut result = Vec::new();
 
             for log_output in logs {
-                let content = String::from_utf8_lossy(&log_output.into_bytes()).to_string();
-
-                // Parse the log line to extract timestamp and content
-                let (timestamp, cleaned_content) = if content.starts_with(char::is_numeric) {
-                    // Docker log format: "2024-01-01T12:00:00.000000000Z message"
-                    if let Some(space_pos) = content.find(' ') {
-                        let (ts, msg) = content.split_at(space_pos);
-                        (Some(ts.to_string()), msg.trim_start().to_string())
-                    } else {
-                        (None, content)
-                    }
-                } else {
-                    (None, content)
-                };
-
-                result.push(ContainerLogLine {
-                    timestamp,
-                    stream: "stdout".to_string(), // We'll improve this logic later
-                    content: cleaned_content,
-                });
+                result.extend(convert_log_output(log_output));
             }
 
             log_docker_operation(
@@ -505,7 +1046,172 @@ pub async fn get_container_logs(
     }
 }
 
+/// Converts a logs `since`/`until` value to the daemon's unix seconds, with 0
+/// meaning unset
+fn log_time(name: &str, value: Option<&str>) -> Result<i32> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        None => Ok(0),
+        Some(value) => parse_timestamp(value)
+            .map(|timestamp| timestamp as i32)
+            .ok_or_else(|| DockerError::InvalidInput {
+                message: format!(
+                    "Invalid {name} '{value}': expected an RFC 3339 date, a unix timestamp or a duration such as 10m"
+                ),
+            }),
+    }
+}
+
+/// Follows a container's log stream in the background, emitting each line as a
+/// `container-logs:{subscription_id}` event. A final `container-logs-end:{subscription_id}`
+/// event (carrying the error message, if any) is emitted when the stream closes,
+/// e.g. because the container stopped. If the daemon connection drops, the
+/// stream resumes from the last delivered line once the host is restored.
+pub async fn subscribe_container_logs(
+    app: AppHandle,
+    host: Option<&str>,
+    id: &str,
+    tail: Option<String>,
+    since: Option<String>,
+) -> Result<String> {
+    let since = log_time("since", since.as_deref())?;
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    // Surface unknown containers to the caller instead of as an immediate end event
+    if let Err(e) = client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await
+    {
+        log_docker_operation("subscribe_container_logs", false, Some(&e.to_string()));
+        return if e.to_string().contains("404") {
+            Err(DockerError::ContainerNotFound { id: id.to_string() })
+        } else {
+            Err(DockerError::Connection(e))
+        };
+    }
+
+    let host_id = DOCKER_CLIENT.host_id(host).await.unwrap_or_default();
+    let follow_options = |tail: String, since: i32| {
+        Some(LogsOptions {
+            stdout: true,
+            stderr: true,
+            follow: true,
+            timestamps: true,
+            tail,
+            since,
+            ..Default::default()
+        })
+    };
+    let mut stream = client.logs(
+        id,
+        follow_options(tail.unwrap_or_else(|| "100".to_string()), since),
+    );
+
+    let container_id = id.to_string();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("logs", |subscription_id| async move {
+            let line_event = format!("container-logs:{subscription_id}");
+            let mut client = client;
+            let mut last_seen: Option<chrono::DateTime<chrono::FixedOffset>> = None;
+            let mut error = None;
+
+            'follow: loop {
+                match stream.next().await {
+                    Some(Ok(log_output)) => {
+                        for line in convert_log_output(log_output) {
+                            // Lines already sent before a reconnect are replayed by `since`
+                            let timestamp = line
+                                .timestamp
+                                .as_deref()
+                                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok());
+                            if timestamp.is_some() && timestamp <= last_seen {
+                                continue;
+                            }
+                            last_seen = timestamp.or(last_seen);
+                            if let Err(e) = app.emit(&line_event, line) {
+                                warn!("Failed to emit log line for {}: {}", subscription_id, e);
+                                break 'follow;
+                            }
+                        }
+                    }
+                    // A dropped connection can also just end the stream
+                    ended => {
+                        let failure = ended.and_then(|item| item.err()).map(|e| e.to_string());
+                        match DOCKER_CLIENT.wait_for_reconnect(&host_id, &client).await {
+                            Some(restored) => {
+                                info!("Resuming log subscription {}", subscription_id);
+                                let since = last_seen.map(|t| t.timestamp() as i32).unwrap_or(0);
+                                client = restored;
+                                stream = client
+                                    .logs(&container_id, follow_options("all".to_string(), since));
+                            }
+                            None => {
+                                error = failure;
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let _ = app.emit(&format!("container-logs-end:{subscription_id}"), error);
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_container_logs",
+        true,
+        Some(&format!(
+            "Following logs for container {id} as {subscription_id}"
+        )),
+    );
+    Ok(subscription_id)
+}
+
+pub async fn unsubscribe_container_logs(subscription_id: &str) -> Result<()> {
+    let cancelled = SUBSCRIPTIONS.cancel(subscription_id).await;
+    log_docker_operation(
+        "unsubscribe_container_logs",
+        true,
+        Some(&if cancelled {
+            format!("Stopped following logs for {subscription_id}")
+        } else {
+            format!("Log subscription {subscription_id} had already ended")
+        }),
+    );
+    Ok(())
+}
+
 // Helper functions to convert from bollard types to our types
+fn convert_log_output(log_output: LogOutput) -> Vec<ContainerLogLine> {
+    let stream = match &log_output {
+        LogOutput::StdOut { .. } => "stdout",
+        LogOutput::StdErr { .. } => "stderr",
+        LogOutput::StdIn { .. } => "stdin",
+        LogOutput::Console { .. } => "console", // TTY containers have no stdout/stderr split
+    };
+    let content = String::from_utf8_lossy(&log_output.into_bytes()).to_string();
+
+    // A single chunk may hold several lines, each carrying its own timestamp prefix
+    content
+        .lines()
+        .map(|line| {
+            // Docker log format: "2024-01-01T12:00:00.000000000Z message"
+            let (timestamp, message) = match line.split_once(' ') {
+                Some((ts, msg)) if chrono::DateTime::parse_from_rfc3339(ts).is_ok() => {
+                    (Some(ts.to_string()), msg.to_string())
+                }
+                _ => (None, line.to_string()),
+            };
+
+            ContainerLogLine {
+                timestamp,
+                stream: stream.to_string(),
+                content: message,
+            }
+        })
+        .collect()
+}
+
 fn convert_container_summary(container: ContainerSummary) -> ContainerListItem {
     ContainerListItem {
         id: container.id.unwrap_or_default(),
@@ -514,7 +1220,10 @@ fn convert_container_summary(container: ContainerSummary) -> ContainerListItem {
         image_id: container.image_id.unwrap_or_default(),
         command: container.command.unwrap_or_default(),
         created: container.created.unwrap_or_default(),
-        state: container.state.unwrap_or_default(),
+        state: container
+            .state
+            .map(|state| state.to_string())
+            .unwrap_or_default(),
         status: container.status.unwrap_or_default(),
         ports: container
             .ports
@@ -686,15 +1395,15 @@ fn convert_container_inspect(container: ContainerInspectResponse) -> ContainerDe
     }
 }
 
-fn convert_container_stats(stats: Stats) -> ContainerStatsData {
+fn convert_container_stats(stats: ContainerStatsResponse) -> ContainerStatsData {
     ContainerStatsData {
-        id: stats.id,
-        name: stats.name,
-        read: stats.read,
-        preread: stats.preread,
+        id: stats.id.unwrap_or_default(),
+        name: stats.name.unwrap_or_default(),
+        read: stats.read.unwrap_or_default(),
+        preread: stats.preread.unwrap_or_default(),
         pids_stats: Some(serde_json::to_value(stats.pids_stats).unwrap_or_default()),
         blkio_stats: Some(serde_json::to_value(&stats.blkio_stats).unwrap_or_default()),
-        num_procs: stats.num_procs,
+        num_procs: stats.num_procs.unwrap_or_default(),
         storage_stats: Some(serde_json::to_value(stats.storage_stats).unwrap_or_default()),
         cpu_stats: Some(serde_json::to_value(&stats.cpu_stats).unwrap_or_default()),
         precpu_stats: Some(serde_json::to_value(&stats.precpu_stats).unwrap_or_default()),
@@ -1,7 +1,151 @@
-// TODO: Events operations
-use crate::utils::error::DockerResult;
+use crate::docker::client::DOCKER_CLIENT;
+use crate::utils::{log_docker_operation, Result};
+use bollard::models::EventMessage;
+use bollard::query_parameters::EventsOptions;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
 
-pub async fn get_docker_events() -> DockerResult<()> {
-    // Events operations implementation
-    Ok(())
+pub const DOCKER_EVENT: &str = "docker-event";
+pub const DOCKER_EVENTS_END: &str = "docker-events-end";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerEvent {
+    pub host: String,   // host id the event came from
+    pub r#type: String, // "container", "image", "network", "volume", ...
+    pub action: String,
+    pub actor: DockerEventActor,
+    pub scope: String,
+    pub time: i64,
+    pub time_nano: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerEventActor {
+    pub id: String,
+    pub attributes: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerEventsEnd {
+    pub host: String,
+    pub error: Option<String>,
+}
+
+/// Owns one daemon event stream per connected host and rebroadcasts every
+/// message to the frontend as a `docker-event`.
+pub struct EventWatcher {
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl EventWatcher {
+    pub fn new() -> Self {
+        Self {
+            tasks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts watching a host's events, replacing any stream left over from a
+    /// previous connection to it.
+    pub async fn start(&self, app: AppHandle, host: Option<&str>) -> Result<()> {
+        let client = DOCKER_CLIENT.get_host_client(host).await?;
+        let host_id = DOCKER_CLIENT.host_id(host).await.unwrap_or_default();
+        let mut stream = client.events(None::<EventsOptions>);
+
+        let mut tasks = self.tasks.lock().await;
+        if let Some(previous) = tasks.remove(&host_id) {
+            previous.abort();
+        }
+
+        let task_host = host_id.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            let mut error = None;
+
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(message) => {
+                        let event = convert_event_message(&task_host, message);
+                        if let Err(e) = app.emit(DOCKER_EVENT, event) {
+                            warn!("Failed to emit docker event: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+
+            info!("Docker event stream for '{}' closed", task_host);
+            let _ = app.emit(
+                DOCKER_EVENTS_END,
+                DockerEventsEnd {
+                    host: task_host,
+                    error,
+                },
+            );
+        });
+        tasks.insert(host_id.clone(), task);
+
+        log_docker_operation(
+            "start_event_stream",
+            true,
+            Some(&format!("Watching events of host '{host_id}'")),
+        );
+        Ok(())
+    }
+
+    pub async fn stop(&self, host: Option<&str>) {
+        let Some(host_id) = DOCKER_CLIENT.host_id(host).await else {
+            return;
+        };
+        if let Some(task) = self.tasks.lock().await.remove(&host_id) {
+            task.abort();
+            log_docker_operation(
+                "stop_event_stream",
+                true,
+                Some(&format!("Stopped watching events of host '{host_id}'")),
+            );
+        }
+    }
+}
+
+impl Default for EventWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Global event watcher, one stream per Docker connection
+lazy_static::lazy_static! {
+    pub static ref DOCKER_EVENTS: EventWatcher = EventWatcher::new();
+}
+
+pub async fn start_event_stream(app: AppHandle, host: Option<&str>) -> Result<()> {
+    DOCKER_EVENTS.start(app, host).await
+}
+
+pub async fn stop_event_stream(host: Option<&str>) {
+    DOCKER_EVENTS.stop(host).await
+}
+
+fn convert_event_message(host: &str, message: EventMessage) -> DockerEvent {
+    let actor = message.actor.unwrap_or_default();
+
+    DockerEvent {
+        host: host.to_string(),
+        r#type: message.typ.map(|t| t.to_string()).unwrap_or_default(),
+        action: message.action.unwrap_or_default(),
+        actor: DockerEventActor {
+            id: actor.id.unwrap_or_default(),
+            attributes: actor.attributes.unwrap_or_default(),
+        },
+        scope: message.scope.map(|s| s.to_string()).unwrap_or_default(),
+        time: message.time.unwrap_or_default(),
+        time_nano: message.time_nano.unwrap_or_default(),
+    }
 }
@@ -1,10 +1,31 @@
+use crate::docker::build_context::{base_images, BuildContext, DEFAULT_DOCKERFILE};
 use crate::docker::client::DOCKER_CLIENT;
-use crate::utils::{log_docker_operation, DockerError, Result};
-use bollard::image::{CreateImageOptions, ListImagesOptions, RemoveImageOptions};
-use bollard::models::{ImageInspect as BollardImageInspect, ImageSummary as BollardImageSummary};
-use futures_util::stream::TryStreamExt;
+use crate::docker::registry::{
+    normalize_image_reference, registry_host, repository_path, resolve_credentials,
+    DOCKER_HUB_REGISTRY,
+};
+use crate::docker::subscriptions::SUBSCRIPTIONS;
+use crate::utils::{log_docker_operation, parse_timestamp, DockerError, Result};
+use bollard::body_try_stream;
+use bollard::models::{
+    CreateImageInfo, ImageInspect as BollardImageInspect, ImageSummary as BollardImageSummary,
+};
+use bollard::query_parameters::{
+    BuildImageOptions, CreateImageOptions, ImportImageOptions, ListContainersOptions,
+    ListImagesOptions, PruneImagesOptions, PushImageOptions, RemoveImageOptions, TagImageOptions,
+};
+use futures_util::stream::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::warn;
+
+// Archive progress is emitted at most once per this many bytes
+const ARCHIVE_PROGRESS_STEP: u64 = 4 * 1024 * 1024;
+const ARCHIVE_CHUNK_SIZE: usize = 256 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageListItem {
@@ -28,8 +49,6 @@ pub struct ImageDetails {
     pub parent: String,
     pub comment: String,
     pub created: String,
-    pub container: String,
-    pub container_config: serde_json::Value,
     pub docker_version: String,
     pub author: String,
     pub config: serde_json::Value,
@@ -59,10 +78,100 @@ pub struct ImageMetadata {
     pub last_tag_time: String,
 }
 
-pub async fn list_images(all: bool) -> Result<Vec<ImageListItem>> {
-    let client = DOCKER_CLIENT.get_client().await?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePullProgress {
+    pub layer_id: Option<String>,
+    pub status: String, // "Downloading", "Extracting", "Pull complete", ...
+    pub progress: Option<String>, // the daemon's progress bar text
+    pub current: Option<i64>,
+    pub total: Option<i64>,
+    pub layers_total: usize,
+    pub layers_done: usize,
+    pub percent: f64, // aggregate over all layers seen so far
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePushProgress {
+    pub status: String, // "Preparing", "Pushing", "Pushed", "Layer already exists", ...
+    pub progress: Option<String>,
+    pub current: Option<i64>,
+    pub total: Option<i64>,
+}
+
+/// What to build and how, as sent by the build dialog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageBuildRequest {
+    pub context_dir: String,
+    pub dockerfile: Option<String>, // relative to the context, `Dockerfile` by default
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub build_args: HashMap<String, String>,
+    pub target: Option<String>,
+    pub platform: Option<String>,
+    #[serde(default)]
+    pub no_cache: bool,
+    #[serde(default)]
+    pub pull: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageBuildProgress {
+    pub stream: Option<String>, // a build log line, e.g. "Step 2/5 : RUN make"
+    pub status: Option<String>, // pull status of a base image
+    pub progress: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageBuildEnd {
+    pub image_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageArchiveProgress {
+    pub bytes: u64,
+    pub total_bytes: u64, // for saves, estimated from the image sizes
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageLoadEnd {
+    pub loaded: Vec<String>, // tags, or ids for untagged images
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePruneResponse {
+    pub images_deleted: Option<Vec<String>>,
+    pub space_reclaimed: Option<u64>,
+    pub dry_run: bool, // nothing was removed; the fields list what would be
+}
+
+/// One layer of an image's history, newest first like `docker history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageHistoryEntry {
+    pub id: String, // "<missing>" for layers built elsewhere
+    pub created: i64,
+    pub created_by: String,
+    pub tags: Vec<String>,
+    pub size: i64,
+    pub comment: String,
+    pub cumulative_size: i64, // this layer plus every layer below it
+}
+
+/// Outcome of retagging one image tag for another registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageRetagResult {
+    pub source: String,
+    pub target: String,
+    pub error: Option<String>,
+}
+
+pub async fn list_images(host: Option<&str>, all: bool) -> Result<Vec<ImageListItem>> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
-    let options = Some(ListImagesOptions::<String> {
+    let options = Some(ListImagesOptions {
         all,
         ..Default::default()
     });
@@ -86,8 +195,8 @@ pub async fn list_images(all: bool) -> Result<Vec<ImageListItem>> {
     }
 }
 
-pub async fn inspect_image(id: &str) -> Result<ImageDetails> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn inspect_image(host: Option<&str>, id: &str) -> Result<ImageDetails> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     match client.inspect_image(id).await {
         Ok(image) => {
@@ -110,8 +219,8 @@ pub async fn inspect_image(id: &str) -> Result<ImageDetails> {
     }
 }
 
-pub async fn remove_image(id: &str, force: bool, no_prune: bool) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn remove_image(host: Option<&str>, id: &str, force: bool, no_prune: bool) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let options = Some(RemoveImageOptions {
         force,
@@ -134,8 +243,356 @@ pub async fn remove_image(id: &str, force: bool, no_prune: bool) -> Result<()> {
     }
 }
 
-pub async fn pull_image(name: &str, tag: Option<&str>) -> Result<()> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn image_history(host: Option<&str>, id: &str) -> Result<Vec<ImageHistoryEntry>> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    match client.image_history(id).await {
+        Ok(history) => {
+            let mut cumulative_size = 0;
+            let mut entries: Vec<ImageHistoryEntry> = history
+                .into_iter()
+                .rev()
+                .map(|item| {
+                    cumulative_size += item.size;
+                    ImageHistoryEntry {
+                        id: item.id,
+                        created: item.created,
+                        created_by: item.created_by,
+                        tags: item.tags,
+                        size: item.size,
+                        comment: item.comment,
+                        cumulative_size,
+                    }
+                })
+                .collect();
+            entries.reverse();
+
+            log_docker_operation(
+                "image_history",
+                true,
+                Some(&format!("Found {} layers for image {id}", entries.len())),
+            );
+            Ok(entries)
+        }
+        Err(e) => {
+            log_docker_operation("image_history", false, Some(&e.to_string()));
+            if e.to_string().contains("404") {
+                Err(DockerError::ImageNotFound { id: id.to_string() })
+            } else {
+                Err(DockerError::Connection(e))
+            }
+        }
+    }
+}
+
+/// Adds `reference` (`repo[:tag]`) as a tag of the image `id`
+pub async fn tag_image(host: Option<&str>, id: &str, reference: &str) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let (repo, tag) = split_image_reference(reference)?;
+    if repo.is_empty() {
+        return Err(DockerError::InvalidInput {
+            message: "Repository name is required".to_string(),
+        });
+    }
+
+    match client
+        .tag_image(
+            id,
+            Some(TagImageOptions {
+                repo: Some(repo),
+                tag: Some(tag),
+            }),
+        )
+        .await
+    {
+        Ok(_) => {
+            log_docker_operation(
+                "tag_image",
+                true,
+                Some(&format!("Tagged image {id} as {reference}")),
+            );
+            Ok(())
+        }
+        Err(e) => {
+            log_docker_operation("tag_image", false, Some(&e.to_string()));
+            if e.to_string().contains("404") {
+                Err(DockerError::ImageNotFound { id: id.to_string() })
+            } else {
+                Err(DockerError::Connection(e))
+            }
+        }
+    }
+}
+
+/// Removes a single tag. Unlike `remove_image`, this refuses to touch the
+/// last tag of an image so the image itself is never deleted.
+pub async fn untag_image(host: Option<&str>, reference: &str) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let image = match client.inspect_image(reference).await {
+        Ok(image) => image,
+        Err(e) => {
+            log_docker_operation("untag_image", false, Some(&e.to_string()));
+            return if e.to_string().contains("404") {
+                Err(DockerError::ImageNotFound {
+                    id: reference.to_string(),
+                })
+            } else {
+                Err(DockerError::Connection(e))
+            };
+        }
+    };
+
+    split_image_reference(reference)?;
+    let wanted = canonical_tag(reference);
+    let repo_tags = image.repo_tags.unwrap_or_default();
+    let Some(reference) = repo_tags
+        .iter()
+        .find(|tag| canonical_tag(tag) == wanted)
+        .cloned()
+    else {
+        return Err(DockerError::InvalidInput {
+            message: format!("{reference} is not a tag of this image"),
+        });
+    };
+    if repo_tags.len() == 1 {
+        return Err(DockerError::InvalidInput {
+            message: format!("{reference} is the only tag of its image; remove the image instead"),
+        });
+    }
+
+    let options = Some(RemoveImageOptions {
+        force: false,
+        noprune: true,
+    });
+    match client.remove_image(&reference, options, None).await {
+        Ok(_) => {
+            log_docker_operation(
+                "untag_image",
+                true,
+                Some(&format!("Removed tag {reference}")),
+            );
+            Ok(())
+        }
+        Err(e) => {
+            log_docker_operation("untag_image", false, Some(&e.to_string()));
+            Err(DockerError::Connection(e))
+        }
+    }
+}
+
+/// Tags each source image again under `prefix`, replacing its registry, so
+/// `ghcr.io/org/app:1.0` becomes `{prefix}/org/app:1.0`. A source given by id
+/// is retagged for all of its tags. Failures are reported per tag.
+pub async fn retag_images(
+    host: Option<&str>,
+    sources: &[String],
+    prefix: &str,
+) -> Result<Vec<ImageRetagResult>> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let prefix = prefix.trim().trim_end_matches('/');
+    if prefix.is_empty() {
+        return Err(DockerError::InvalidInput {
+            message: "Target registry prefix is required".to_string(),
+        });
+    }
+
+    let mut results = Vec::new();
+    for source in sources {
+        let repo_tags = match client.inspect_image(source).await {
+            Ok(image) => image.repo_tags.unwrap_or_default(),
+            Err(e) => {
+                results.push(ImageRetagResult {
+                    source: source.clone(),
+                    target: String::new(),
+                    error: Some(e.to_string()),
+                });
+                continue;
+            }
+        };
+
+        // A source given by id or digest stands for all of its tags
+        let source_tag = split_image_reference(source)
+            .ok()
+            .map(|(repo, tag)| format!("{repo}:{tag}"))
+            .filter(|source_tag| repo_tags.contains(source_tag));
+        let tags = match source_tag {
+            Some(source_tag) => vec![source_tag],
+            None => repo_tags,
+        };
+
+        for tag in tags {
+            let target = format!("{prefix}/{}", repository_path(&tag));
+            let error = match split_image_reference(&target) {
+                Ok((repo, new_tag)) => client
+                    .tag_image(
+                        &tag,
+                        Some(TagImageOptions {
+                            repo: Some(repo),
+                            tag: Some(new_tag),
+                        }),
+                    )
+                    .await
+                    .err()
+                    .map(|e| e.to_string()),
+                Err(e) => Some(e.to_string()),
+            };
+            results.push(ImageRetagResult {
+                source: tag,
+                target,
+                error,
+            });
+        }
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    log_docker_operation(
+        "retag_images",
+        failed == 0,
+        Some(&format!(
+            "Retagged {} of {} tags under {prefix}",
+            results.len() - failed,
+            results.len()
+        )),
+    );
+    Ok(results)
+}
+
+/// Removes dangling images, or with `all` every image no container uses.
+/// `until` (a duration such as `24h` or a timestamp) keeps images created
+/// after it, and `labels` (`key` or `key=value`) limits the prune to images
+/// carrying all of them. A dry run reports what would be removed instead;
+/// it is an estimate, as the daemon offers no dry run. The daemon applies
+/// the dangling and label filters to the listing just as it does to the
+/// prune, while `until` and the in-use check are evaluated here.
+pub async fn prune_images(
+    host: Option<&str>,
+    all: bool,
+    until: Option<&str>,
+    labels: &[String],
+    dry_run: bool,
+) -> Result<ImagePruneResponse> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let until = until.map(str::trim).filter(|until| !until.is_empty());
+    let cutoff = until.map(parse_until).transpose()?;
+
+    let mut filters = HashMap::new();
+    if !labels.is_empty() {
+        filters.insert("label".to_string(), labels.to_vec());
+    }
+
+    if dry_run {
+        // For listing, `dangling=false` would mean tagged images only
+        let mut list_filters = filters.clone();
+        if !all {
+            list_filters.insert("dangling".to_string(), vec!["true".to_string()]);
+        }
+        let images = client
+            .list_images(Some(ListImagesOptions {
+                all: false,
+                filters: Some(list_filters),
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| {
+                log_docker_operation("prune_images", false, Some(&e.to_string()));
+                DockerError::Connection(e)
+            })?;
+        // Stopped containers keep their image in use too
+        let containers = client
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| {
+                log_docker_operation("prune_images", false, Some(&e.to_string()));
+                DockerError::Connection(e)
+            })?;
+        let used: Vec<String> = containers
+            .into_iter()
+            .filter_map(|container| container.image_id)
+            .collect();
+
+        let candidates: Vec<BollardImageSummary> = images
+            .into_iter()
+            .filter(|image| !used.contains(&image.id))
+            .filter(|image| cutoff.is_none_or(|cutoff| image.created < cutoff))
+            .collect();
+        let space_reclaimed = candidates
+            .iter()
+            .map(|image| {
+                let size = if image.shared_size > 0 {
+                    image.size - image.shared_size
+                } else {
+                    image.size
+                };
+                size.max(0) as u64
+            })
+            .sum();
+        let images_deleted: Vec<String> = candidates.into_iter().map(|image| image.id).collect();
+
+        log_docker_operation(
+            "prune_images",
+            true,
+            Some(&format!(
+                "Dry run: {} images would be removed, reclaiming about {space_reclaimed} bytes",
+                images_deleted.len()
+            )),
+        );
+        return Ok(ImagePruneResponse {
+            images_deleted: Some(images_deleted),
+            space_reclaimed: Some(space_reclaimed),
+            dry_run: true,
+        });
+    }
+
+    filters.insert("dangling".to_string(), vec![(!all).to_string()]);
+    if let Some(until) = until {
+        filters.insert("until".to_string(), vec![until.to_string()]);
+    }
+
+    match client
+        .prune_images(Some(PruneImagesOptions {
+            filters: Some(filters),
+        }))
+        .await
+    {
+        Ok(response) => {
+            let images_deleted: Option<Vec<String>> = response
+                .images_deleted
+                .map(|items| items.into_iter().filter_map(|item| item.deleted).collect());
+            let space_reclaimed = response.space_reclaimed.map(|v| v as u64);
+
+            log_docker_operation(
+                "prune_images",
+                true,
+                Some(&format!(
+                    "Removed {} images, reclaiming {} bytes",
+                    images_deleted.as_ref().map_or(0, Vec::len),
+                    space_reclaimed.unwrap_or_default()
+                )),
+            );
+            Ok(ImagePruneResponse {
+                images_deleted,
+                space_reclaimed,
+                dry_run: false,
+            })
+        }
+        Err(e) => {
+            log_docker_operation("prune_images", false, Some(&e.to_string()));
+            Err(DockerError::OperationFailed {
+                message: format!("Failed to prune images: {e}"),
+            })
+        }
+    }
+}
+
+pub async fn pull_image(host: Option<&str>, name: &str, tag: Option<&str>) -> Result<()> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     let image_name = if let Some(tag) = tag {
         format!("{name}:{tag}")
@@ -144,12 +601,13 @@ pub async fn pull_image(name: &str, tag: Option<&str>) -> Result<()> {
     };
 
     let options = Some(CreateImageOptions {
-        from_image: image_name.clone(),
+        from_image: Some(image_name.clone()),
         ..Default::default()
     });
 
+    let credentials = resolve_credentials(&image_name).await;
     match client
-        .create_image(options, None, None)
+        .create_image(options, None, credentials)
         .try_collect::<Vec<_>>()
         .await
     {
@@ -168,6 +626,724 @@ pub async fn pull_image(name: &str, tag: Option<&str>) -> Result<()> {
     }
 }
 
+/// Pulls an image in the background, emitting an `ImagePullProgress` as
+/// `image-pull-progress:{subscription_id}` for every status message the
+/// daemon sends. A final `image-pull-end:{subscription_id}` event carries the
+/// error message, if any. Cancelling the subscription aborts the pull.
+pub async fn subscribe_image_pull(
+    app: AppHandle,
+    host: Option<&str>,
+    name: &str,
+    tag: Option<&str>,
+) -> Result<String> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let image_name = if let Some(tag) = tag {
+        format!("{name}:{tag}")
+    } else {
+        name.to_string()
+    };
+
+    let options = Some(CreateImageOptions {
+        from_image: Some(image_name.clone()),
+        ..Default::default()
+    });
+    let credentials = resolve_credentials(&image_name).await;
+    let mut stream = client.create_image(options, None, credentials);
+
+    let task_image = image_name.clone();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("pull", |subscription_id| async move {
+            let progress_event = format!("image-pull-progress:{subscription_id}");
+            let mut layers: BTreeMap<String, f64> = BTreeMap::new();
+            let mut error = None;
+
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(info) => {
+                        if let Some(message) = info.error {
+                            error = Some(message);
+                            break;
+                        }
+                        let progress = convert_pull_progress(info, &mut layers);
+                        if let Err(e) = app.emit(&progress_event, progress) {
+                            warn!(
+                                "Failed to emit pull progress for {}: {}",
+                                subscription_id, e
+                            );
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+
+            log_docker_operation(
+                "pull_image",
+                error.is_none(),
+                Some(&match &error {
+                    None => format!("Pulled image {task_image}"),
+                    Some(e) => format!("Pulling {task_image} failed: {e}"),
+                }),
+            );
+            let _ = app.emit(&format!("image-pull-end:{subscription_id}"), error);
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_image_pull",
+        true,
+        Some(&format!("Pulling image {image_name} as {subscription_id}")),
+    );
+    Ok(subscription_id)
+}
+
+pub async fn cancel_image_pull(subscription_id: &str) -> Result<()> {
+    let cancelled = SUBSCRIPTIONS.cancel(subscription_id).await;
+    log_docker_operation(
+        "cancel_image_pull",
+        true,
+        Some(&if cancelled {
+            format!("Cancelled pull {subscription_id}")
+        } else {
+            format!("Pull {subscription_id} had already finished")
+        }),
+    );
+    Ok(())
+}
+
+/// Pushes an image to its registry in the background, emitting an
+/// `ImagePushProgress` as `image-push-progress:{subscription_id}` for every
+/// status message and a final `image-push-end:{subscription_id}` event with
+/// the error message, if any.
+pub async fn subscribe_image_push(
+    app: AppHandle,
+    host: Option<&str>,
+    name: &str,
+    tag: Option<&str>,
+) -> Result<String> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let tag = tag.unwrap_or("latest").to_string();
+    let image_name = format!("{name}:{tag}");
+
+    // Fail early for unknown images instead of through the end event
+    if let Err(e) = client.inspect_image(&image_name).await {
+        log_docker_operation("subscribe_image_push", false, Some(&e.to_string()));
+        return if e.to_string().contains("404") {
+            Err(DockerError::ImageNotFound { id: image_name })
+        } else {
+            Err(DockerError::Connection(e))
+        };
+    }
+
+    let options = Some(PushImageOptions {
+        tag: Some(tag),
+        ..Default::default()
+    });
+    let credentials = resolve_credentials(name).await;
+    let mut stream = client.push_image(name, options, credentials);
+
+    let task_image = image_name.clone();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("push", |subscription_id| async move {
+            let progress_event = format!("image-push-progress:{subscription_id}");
+            let mut error = None;
+
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(info) => {
+                        if let Some(message) = info.error {
+                            error = Some(message);
+                            break;
+                        }
+                        let (current, total) = info
+                            .progress_detail
+                            .map(|detail| (detail.current, detail.total))
+                            .unwrap_or_default();
+                        let progress = ImagePushProgress {
+                            status: info.status.unwrap_or_default(),
+                            progress: info.progress,
+                            current,
+                            total,
+                        };
+                        if let Err(e) = app.emit(&progress_event, progress) {
+                            warn!(
+                                "Failed to emit push progress for {}: {}",
+                                subscription_id, e
+                            );
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+
+            log_docker_operation(
+                "push_image",
+                error.is_none(),
+                Some(&match &error {
+                    None => format!("Pushed image {task_image}"),
+                    Some(e) => format!("Pushing {task_image} failed: {e}"),
+                }),
+            );
+            let _ = app.emit(&format!("image-push-end:{subscription_id}"), error);
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_image_push",
+        true,
+        Some(&format!("Pushing image {image_name} as {subscription_id}")),
+    );
+    Ok(subscription_id)
+}
+
+/// Builds an image from a local context directory in the background. The
+/// context is packed honouring `.dockerignore` and streamed to the daemon as
+/// it is packed, then every message of the build is emitted as an `ImageBuildProgress` on
+/// `image-build-progress:{subscription_id}`. A final `ImageBuildEnd` on
+/// `image-build-end:{subscription_id}` carries the built image id or the
+/// error. Cancelling the subscription stops following the build.
+pub async fn subscribe_image_build(
+    app: AppHandle,
+    host: Option<&str>,
+    request: ImageBuildRequest,
+) -> Result<String> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let dockerfile = request
+        .dockerfile
+        .clone()
+        .filter(|dockerfile| !dockerfile.is_empty())
+        .unwrap_or_else(|| DEFAULT_DOCKERFILE.to_string());
+    let context_dir = PathBuf::from(&request.context_dir);
+
+    let context = {
+        let context_dir = context_dir.clone();
+        let dockerfile = dockerfile.clone();
+        tauri::async_runtime::spawn_blocking(move || BuildContext::open(&context_dir, &dockerfile))
+            .await
+            .map_err(|e| DockerError::OperationFailed {
+                message: format!("Reading the build context failed: {e}"),
+            })?
+    };
+    let context = match context {
+        Ok(context) => context,
+        Err(e) => {
+            log_docker_operation("subscribe_image_build", false, Some(&e.to_string()));
+            return Err(e);
+        }
+    };
+
+    // Credentials for private base images go along with the build
+    let mut credentials = HashMap::new();
+    let dockerfile_contents = tokio::fs::read_to_string(context_dir.join(&dockerfile))
+        .await
+        .unwrap_or_default();
+    for image in base_images(&dockerfile_contents) {
+        if let Some(auth) = resolve_credentials(&image).await {
+            if let Some(server) = auth.serveraddress.clone() {
+                credentials.insert(server, auth);
+            }
+        }
+    }
+
+    let mut tags = request.tags.iter().filter(|tag| !tag.is_empty()).cloned();
+    let options = BuildImageOptions {
+        dockerfile: dockerfile.clone(),
+        t: tags.next(),
+        buildargs: Some(request.build_args.clone()),
+        target: request.target.clone().unwrap_or_default(),
+        platform: request.platform.clone().unwrap_or_default(),
+        nocache: request.no_cache,
+        pull: request.pull.then(|| "true".to_string()),
+        rm: true,
+        ..Default::default()
+    };
+    let extra_tags = tags
+        .map(|tag| split_image_reference(&tag))
+        .collect::<Result<Vec<_>>>()?;
+
+    let credentials = (!credentials.is_empty()).then_some(credentials);
+
+    let description = request
+        .tags
+        .first()
+        .cloned()
+        .unwrap_or_else(|| request.context_dir.clone());
+    let task_description = description.clone();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("build", |subscription_id| async move {
+            let progress_event = format!("image-build-progress:{subscription_id}");
+            let pack_error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+            let body = context
+                .into_stream(Arc::clone(&pack_error))
+                .map_ok(Into::into);
+            let mut stream = client.build_image(options, credentials, Some(body_try_stream(body)));
+            let mut image_id = None;
+            let mut error = None;
+
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(info) => {
+                        if let Some(message) = info.error {
+                            error = Some(message);
+                            break;
+                        }
+                        if let Some(id) = info.aux.and_then(|aux| aux.id) {
+                            image_id = Some(id);
+                        }
+                        if info.stream.is_none() && info.status.is_none() {
+                            continue;
+                        }
+                        let progress = ImageBuildProgress {
+                            stream: info.stream,
+                            status: info.status,
+                            progress: info.progress,
+                        };
+                        if let Err(e) = app.emit(&progress_event, progress) {
+                            warn!(
+                                "Failed to emit build progress for {}: {}",
+                                subscription_id, e
+                            );
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+            // A failed pack cuts the upload short; report that over the
+            // connection error it causes
+            if let Some(message) = pack_error.lock().unwrap().take() {
+                error = Some(format!("Packing the build context failed: {message}"));
+            }
+
+            if let (Some(id), None) = (&image_id, &error) {
+                for (repo, tag) in extra_tags {
+                    let options = Some(TagImageOptions {
+                        repo: Some(repo),
+                        tag: Some(tag),
+                    });
+                    if let Err(e) = client.tag_image(id, options).await {
+                        error = Some(format!("Tagging the built image failed: {e}"));
+                        break;
+                    }
+                }
+            }
+
+            log_docker_operation(
+                "build_image",
+                error.is_none(),
+                Some(&match (&image_id, &error) {
+                    (_, Some(e)) => format!("Building {task_description} failed: {e}"),
+                    (Some(id), None) => format!("Built {task_description} as {id}"),
+                    (None, None) => format!("Built {task_description}"),
+                }),
+            );
+            let _ = app.emit(
+                &format!("image-build-end:{subscription_id}"),
+                ImageBuildEnd { image_id, error },
+            );
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_image_build",
+        true,
+        Some(&format!(
+            "Building {description} from {} as {subscription_id}",
+            request.context_dir
+        )),
+    );
+    Ok(subscription_id)
+}
+
+pub async fn cancel_image_build(subscription_id: &str) -> Result<()> {
+    let cancelled = SUBSCRIPTIONS.cancel(subscription_id).await;
+    log_docker_operation(
+        "cancel_image_build",
+        true,
+        Some(&if cancelled {
+            format!("Cancelled build {subscription_id}")
+        } else {
+            format!("Build {subscription_id} had already finished")
+        }),
+    );
+    Ok(())
+}
+
+/// Writes one or more images to a tar archive at `path`, like `docker save`.
+/// The archive is streamed to disk while `ImageArchiveProgress` events are
+/// emitted as `image-save-progress:{subscription_id}`; a final
+/// `image-save-end:{subscription_id}` carries the error message, if any.
+/// The archive is written next to `path` and only renamed into place once
+/// complete, so a failed or cancelled save leaves nothing behind.
+pub async fn subscribe_image_save(
+    app: AppHandle,
+    host: Option<&str>,
+    images: &[String],
+    path: &str,
+) -> Result<String> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    if images.is_empty() {
+        return Err(DockerError::InvalidInput {
+            message: "No images selected".to_string(),
+        });
+    }
+
+    let mut total_bytes = 0;
+    for image in images {
+        match client.inspect_image(image).await {
+            Ok(details) => total_bytes += details.size.unwrap_or_default().max(0) as u64,
+            Err(e) => {
+                log_docker_operation("subscribe_image_save", false, Some(&e.to_string()));
+                return if e.to_string().contains("404") {
+                    Err(DockerError::ImageNotFound { id: image.clone() })
+                } else {
+                    Err(DockerError::Connection(e))
+                };
+            }
+        }
+    }
+
+    let path = PathBuf::from(path);
+    let mut partial_path = path.clone().into_os_string();
+    partial_path.push(".partial");
+    let partial_path = PathBuf::from(partial_path);
+    // Owned by the task from here on, so dropping it in any state cleans up
+    let mut partial = PartialFile(Some(partial_path.clone()));
+    let mut file = tokio::fs::File::create(&partial_path).await?;
+
+    let task_images = images.to_vec();
+    let task_path = path.clone();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("save", |subscription_id| async move {
+            let progress_event = format!("image-save-progress:{subscription_id}");
+            let names: Vec<&str> = task_images.iter().map(String::as_str).collect();
+            let mut stream = client.export_images(&names);
+            let mut written = 0;
+            let mut reported = 0;
+            let mut error = None;
+
+            while let Some(chunk) = stream.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                };
+                if let Err(e) = file.write_all(&chunk).await {
+                    error = Some(e.to_string());
+                    break;
+                }
+                written += chunk.len() as u64;
+                if written - reported >= ARCHIVE_PROGRESS_STEP {
+                    reported = written;
+                    let progress = archive_progress(written, total_bytes.max(written));
+                    if let Err(e) = app.emit(&progress_event, progress) {
+                        warn!(
+                            "Failed to emit save progress for {}: {}",
+                            subscription_id, e
+                        );
+                    }
+                }
+            }
+
+            if error.is_none() {
+                error = file.flush().await.err().map(|e| e.to_string());
+            }
+            drop(file);
+            if error.is_none() {
+                error = tokio::fs::rename(&partial_path, &task_path)
+                    .await
+                    .err()
+                    .map(|e| e.to_string());
+            }
+            if error.is_none() {
+                partial.keep();
+                let _ = app.emit(&progress_event, archive_progress(written, written));
+            }
+
+            log_docker_operation(
+                "save_images",
+                error.is_none(),
+                Some(&match &error {
+                    None => format!(
+                        "Saved {} to {} ({written} bytes)",
+                        task_images.join(", "),
+                        task_path.display()
+                    ),
+                    Some(e) => format!("Saving {} failed: {e}", task_images.join(", ")),
+                }),
+            );
+            let _ = app.emit(&format!("image-save-end:{subscription_id}"), error);
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_image_save",
+        true,
+        Some(&format!(
+            "Saving {} to {} as {subscription_id}",
+            images.join(", "),
+            path.display()
+        )),
+    );
+    Ok(subscription_id)
+}
+
+/// Loads images from a tar archive at `path`, like `docker load`. Upload
+/// progress is emitted as `ImageArchiveProgress` on
+/// `image-load-progress:{subscription_id}` and a final `ImageLoadEnd` on
+/// `image-load-end:{subscription_id}` lists the loaded images.
+pub async fn subscribe_image_load(
+    app: AppHandle,
+    host: Option<&str>,
+    path: &str,
+) -> Result<String> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let file = tokio::fs::File::open(path).await?;
+    let total_bytes = file.metadata().await?.len();
+
+    let task_path = path.to_string();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("load", |subscription_id| async move {
+            let progress_event = format!("image-load-progress:{subscription_id}");
+            let read_error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+
+            let body_app = app.clone();
+            let body_error = Arc::clone(&read_error);
+            let body = futures_util::stream::unfold(
+                (file, 0, 0),
+                move |(mut file, sent, reported): (tokio::fs::File, u64, u64)| {
+                    let app = body_app.clone();
+                    let progress_event = progress_event.clone();
+                    let read_error = Arc::clone(&body_error);
+                    async move {
+                        let mut chunk = vec![0; ARCHIVE_CHUNK_SIZE];
+                        let read = match file.read(&mut chunk).await {
+                            Ok(0) => return None,
+                            Ok(read) => read,
+                            Err(e) => {
+                                *read_error.lock().unwrap() = Some(e.to_string());
+                                return None;
+                            }
+                        };
+                        chunk.truncate(read);
+
+                        let sent = sent + read as u64;
+                        let reported = if sent - reported >= ARCHIVE_PROGRESS_STEP
+                            || sent == total_bytes
+                        {
+                            let _ = app.emit(&progress_event, archive_progress(sent, total_bytes));
+                            sent
+                        } else {
+                            reported
+                        };
+                        Some((chunk.into(), (file, sent, reported)))
+                    }
+                },
+            );
+
+            let options = ImportImageOptions {
+                quiet: true,
+                ..Default::default()
+            };
+            let mut stream = client.import_image_stream(options, body, None);
+            let mut loaded = Vec::new();
+            let mut error = None;
+
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(info) => {
+                        if let Some(message) = info.error {
+                            error = Some(message);
+                            break;
+                        }
+                        // "Loaded image: nginx:latest" or "Loaded image ID: sha256:..."
+                        let line = info.stream.unwrap_or_default();
+                        if let Some((_, image)) = line.split_once("Loaded image") {
+                            let image = image.trim_start_matches(" ID").trim_start_matches(':');
+                            loaded.push(image.trim().to_string());
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+            // A failed read cuts the upload short; report that over the
+            // daemon's complaint about the truncated archive
+            if let Some(message) = read_error.lock().unwrap().take() {
+                error = Some(message);
+            }
+
+            log_docker_operation(
+                "load_images",
+                error.is_none(),
+                Some(&match &error {
+                    None => format!("Loaded {} from {task_path}", loaded.join(", ")),
+                    Some(e) => format!("Loading {task_path} failed: {e}"),
+                }),
+            );
+            let _ = app.emit(
+                &format!("image-load-end:{subscription_id}"),
+                ImageLoadEnd { loaded, error },
+            );
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_image_load",
+        true,
+        Some(&format!(
+            "Loading {path} ({total_bytes} bytes) as {subscription_id}"
+        )),
+    );
+    Ok(subscription_id)
+}
+
+/// Cancels a running save or load
+pub async fn cancel_image_archive(subscription_id: &str) -> Result<()> {
+    let cancelled = SUBSCRIPTIONS.cancel(subscription_id).await;
+    log_docker_operation(
+        "cancel_image_archive",
+        true,
+        Some(&if cancelled {
+            format!("Cancelled {subscription_id}")
+        } else {
+            format!("{subscription_id} had already finished")
+        }),
+    );
+    Ok(())
+}
+
+/// Splits `repo[:tag]` into its repository and tag, defaulting the tag to
+/// `latest`. A colon before the last `/` belongs to a registry port. Digest
+/// references (`repo@sha256:...`) name content rather than a tag and are
+/// rejected.
+fn split_image_reference(reference: &str) -> Result<(String, String)> {
+    if reference.contains('@') {
+        return Err(DockerError::InvalidInput {
+            message: format!("{reference} is a digest reference, not a tag"),
+        });
+    }
+    Ok(match reference.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') => (repo.to_string(), tag.to_string()),
+        _ => (reference.to_string(), "latest".to_string()),
+    })
+}
+
+/// A tag reference in one spelling for comparisons: `nginx`, `nginx:latest`
+/// and `docker.io/library/nginx:latest` all come out as `nginx:latest`
+fn canonical_tag(reference: &str) -> String {
+    let reference = normalize_image_reference(reference);
+    if registry_host(&reference) != DOCKER_HUB_REGISTRY {
+        return reference;
+    }
+    let path = repository_path(&reference);
+    path.strip_prefix("library/").unwrap_or(path).to_string()
+}
+
+/// Resolves a prune `until` filter to a unix timestamp
+fn parse_until(until: &str) -> Result<i64> {
+    parse_timestamp(until).ok_or_else(|| DockerError::InvalidInput {
+        message: format!("Invalid until filter '{until}'"),
+    })
+}
+
+fn archive_progress(bytes: u64, total_bytes: u64) -> ImageArchiveProgress {
+    ImageArchiveProgress {
+        bytes,
+        total_bytes,
+        percent: if total_bytes > 0 {
+            (bytes as f64 / total_bytes as f64 * 100.0).min(100.0)
+        } else {
+            100.0
+        },
+    }
+}
+
+/// Deletes a partially written file when dropped, unless `keep` was called
+struct PartialFile(Option<PathBuf>);
+
+impl PartialFile {
+    fn keep(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for PartialFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Converts a pull status message, updating the per-layer completion used for
+/// the aggregate percentage. Downloading counts for the first half of a
+/// layer and extracting for the second half.
+fn convert_pull_progress(
+    info: CreateImageInfo,
+    layers: &mut BTreeMap<String, f64>,
+) -> ImagePullProgress {
+    let status = info.status.unwrap_or_default();
+    let (current, total) = info
+        .progress_detail
+        .map(|detail| (detail.current, detail.total))
+        .unwrap_or_default();
+    let fraction = match (current, total) {
+        (Some(current), Some(total)) if total > 0 => (current as f64 / total as f64).min(1.0),
+        _ => 0.0,
+    };
+
+    if let Some(layer_id) = &info.id {
+        let completion = match status.as_str() {
+            "Pulling fs layer" | "Waiting" => Some(0.0),
+            "Downloading" => Some(fraction * 0.5),
+            "Verifying Checksum" | "Download complete" => Some(0.5),
+            "Extracting" => Some(0.5 + fraction * 0.5),
+            "Pull complete" | "Already exists" => Some(1.0),
+            _ => None, // e.g. "Pulling from library/nginx", which is not a layer
+        };
+        if let Some(completion) = completion {
+            layers.insert(layer_id.clone(), completion);
+        }
+    }
+
+    let percent = if layers.is_empty() {
+        0.0
+    } else {
+        layers.values().sum::<f64>() / layers.len() as f64 * 100.0
+    };
+
+    ImagePullProgress {
+        layer_id: info.id,
+        status,
+        progress: info.progress,
+        current,
+        total,
+        layers_total: layers.len(),
+        layers_done: layers.values().filter(|c| **c >= 1.0).count(),
+        percent,
+    }
+}
+
 fn convert_image_summary(image: BollardImageSummary) -> ImageListItem {
     ImageListItem {
         id: image.id,
@@ -191,8 +1367,6 @@ fn convert_image_inspect(image: BollardImageInspect) -> ImageDetails {
         parent: image.parent.unwrap_or_default(),
         comment: image.comment.unwrap_or_default(),
         created: image.created.unwrap_or_default(),
-        container: image.container.unwrap_or_default(),
-        container_config: serde_json::to_value(&image.container_config).unwrap_or_default(),
         docker_version: image.docker_version.unwrap_or_default(),
         author: image.author.unwrap_or_default(),
         config: serde_json::to_value(&image.config).unwrap_or_default(),
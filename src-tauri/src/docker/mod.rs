@@ -1,9 +1,24 @@
+pub mod build_context;
 pub mod client;
+pub mod compose;
+pub mod container_export;
+pub mod container_spec;
 pub mod containers;
+pub mod events;
 pub mod images;
+pub mod registry;
+pub mod ssh;
+pub mod stats;
+pub mod subscriptions;
 pub mod system;
 
 pub use client::*;
+pub use compose::*;
+pub use container_export::*;
+pub use container_spec::*;
 pub use containers::*;
+pub use events::*;
 pub use images::*;
+pub use registry::*;
+pub use stats::*;
 pub use system::*;
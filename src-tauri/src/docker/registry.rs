@@ -0,0 +1,397 @@
+use crate::utils::config::{load_config, save_config, RegistryCredential};
+use crate::utils::{log_docker_operation, DockerError, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bollard::auth::DockerCredentials;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::warn;
+
+pub const DOCKER_HUB_REGISTRY: &str = "docker.io";
+
+// Key the docker CLI uses for Docker Hub in config.json and credential helpers
+const DOCKER_HUB_SERVER: &str = "https://index.docker.io/v1/";
+
+// Keychain service app-entered registry secrets are filed under, one entry per registry
+const KEYCHAIN_SERVICE: &str = "com.docsee.app.registry";
+
+/// A known registry login, without its secret
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryLogin {
+    pub registry: String,
+    pub username: Option<String>,
+    pub source: String, // "app", "docker-config" or "credential-helper"
+}
+
+// The parts of ~/.docker/config.json that hold credentials
+#[derive(Debug, Default, Deserialize)]
+struct DockerCliConfig {
+    #[serde(default)]
+    auths: HashMap<String, DockerCliAuth>,
+    #[serde(default, rename = "credHelpers")]
+    cred_helpers: HashMap<String, String>,
+    #[serde(default, rename = "credsStore")]
+    creds_store: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DockerCliAuth {
+    auth: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    identitytoken: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct HelperCredentials {
+    username: String,
+    secret: String,
+}
+
+/// Returns the registry an image reference points at, e.g. `ghcr.io` for
+/// `ghcr.io/org/app:1.0` and `docker.io` for `nginx`.
+pub fn registry_host(image: &str) -> String {
+    match image.split_once('/') {
+        Some((first, _)) if is_registry_component(first) => normalize_registry(first),
+        _ => DOCKER_HUB_REGISTRY.to_string(),
+    }
+}
+
+/// Returns an image reference without its registry, e.g. `org/app:1.0` for
+/// `ghcr.io/org/app:1.0`.
+pub fn repository_path(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((first, rest)) if is_registry_component(first) => rest,
+        _ => image,
+    }
+}
+
+/// Adds `:latest` to untagged references so a pull fetches one tag rather than all of them
+pub fn normalize_image_reference(image: &str) -> String {
+    let last_segment = image.rsplit('/').next().unwrap_or(image);
+    if image.contains('@') || last_segment.contains(':') {
+        image.to_string()
+    } else {
+        format!("{image}:latest")
+    }
+}
+
+// The first path component names a registry rather than a Docker Hub user
+fn is_registry_component(component: &str) -> bool {
+    component.contains('.') || component.contains(':') || component == "localhost"
+}
+
+/// Reduces a registry address or server URL to its host, folding the
+/// various Docker Hub aliases into `docker.io`.
+pub fn normalize_registry(registry: &str) -> String {
+    let host = registry
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match host.as_str() {
+        "index.docker.io" | "registry-1.docker.io" | "registry.hub.docker.com" => {
+            DOCKER_HUB_REGISTRY.to_string()
+        }
+        _ => host,
+    }
+}
+
+/// Looks up credentials for the registry of `image`: app-entered logins
+/// first, then `~/.docker/config.json` auths, then its credential helpers.
+pub async fn resolve_credentials(image: &str) -> Option<DockerCredentials> {
+    let registry = registry_host(image);
+    let server_address = server_address(&registry);
+
+    let stored = load_config().ok().and_then(|config| {
+        config
+            .registries
+            .into_iter()
+            .find(|c| c.registry == registry)
+    });
+    if let Some(stored) = stored {
+        let key = registry.clone();
+        let secret = with_keychain(move || keychain_entry(&key)?.get_password()).await;
+        match secret.ok().or(stored.password) {
+            Some(password) => {
+                return Some(DockerCredentials {
+                    username: Some(stored.username),
+                    password: Some(password),
+                    serveraddress: Some(server_address),
+                    ..Default::default()
+                })
+            }
+            None => warn!("The keychain has no secret for the {registry} login"),
+        }
+    }
+
+    let cli_config = read_docker_cli_config();
+
+    if let Some(auth) = cli_config
+        .auths
+        .iter()
+        .find(|(key, _)| normalize_registry(key) == registry)
+        .map(|(_, auth)| auth)
+    {
+        let (username, password) = match &auth.auth {
+            Some(encoded) => decode_auth(encoded).unwrap_or_default(),
+            None => (
+                auth.username.clone().unwrap_or_default(),
+                auth.password.clone().unwrap_or_default(),
+            ),
+        };
+        if !username.is_empty() || auth.identitytoken.is_some() {
+            return Some(DockerCredentials {
+                username: Some(username),
+                password: Some(password),
+                identitytoken: auth.identitytoken.clone(),
+                serveraddress: Some(server_address),
+                ..Default::default()
+            });
+        }
+    }
+
+    let helper = cli_config
+        .cred_helpers
+        .iter()
+        .find(|(key, _)| normalize_registry(key) == registry)
+        .map(|(_, helper)| helper.clone())
+        .or(cli_config.creds_store)?;
+
+    let output = run_credential_helper(&helper, "get", &server_address).await?;
+    let helper_credentials: HelperCredentials = serde_json::from_str(&output).ok()?;
+
+    // Helpers report identity tokens with the "<token>" username
+    Some(if helper_credentials.username == "<token>" {
+        DockerCredentials {
+            identitytoken: Some(helper_credentials.secret),
+            serveraddress: Some(server_address),
+            ..Default::default()
+        }
+    } else {
+        DockerCredentials {
+            username: Some(helper_credentials.username),
+            password: Some(helper_credentials.secret),
+            serveraddress: Some(server_address),
+            ..Default::default()
+        }
+    })
+}
+
+pub async fn list_registry_logins() -> Result<Vec<RegistryLogin>> {
+    let mut logins: Vec<RegistryLogin> = load_config()?
+        .registries
+        .into_iter()
+        .map(|c| RegistryLogin {
+            registry: c.registry,
+            username: Some(c.username),
+            source: "app".to_string(),
+        })
+        .collect();
+
+    let cli_config = read_docker_cli_config();
+    let mut push = |registry: String, username: Option<String>, source: &str| {
+        if !logins.iter().any(|l| l.registry == registry) {
+            logins.push(RegistryLogin {
+                registry,
+                username,
+                source: source.to_string(),
+            });
+        }
+    };
+
+    for (key, auth) in &cli_config.auths {
+        let username = match &auth.auth {
+            Some(encoded) => decode_auth(encoded).map(|(username, _)| username),
+            None => auth.username.clone(),
+        };
+        // Entries without inline auth belong to the credential store
+        if username.is_some() || auth.identitytoken.is_some() {
+            push(normalize_registry(key), username, "docker-config");
+        }
+    }
+    for key in cli_config.cred_helpers.keys() {
+        push(normalize_registry(key), None, "credential-helper");
+    }
+    if let Some(store) = &cli_config.creds_store {
+        let listed = run_credential_helper(store, "list", "")
+            .await
+            .and_then(|output| serde_json::from_str::<HashMap<String, String>>(&output).ok())
+            .unwrap_or_default();
+        for (server, username) in listed {
+            push(
+                normalize_registry(&server),
+                Some(username),
+                "credential-helper",
+            );
+        }
+    }
+
+    logins.sort_by(|a, b| a.registry.cmp(&b.registry));
+    Ok(logins)
+}
+
+/// Stores app-entered credentials, replacing any previous login for the
+/// registry. The secret goes to the keychain, the username to the settings.
+pub async fn save_registry_login(registry: &str, username: &str, password: &str) -> Result<()> {
+    let registry = normalize_registry(registry);
+    if registry.is_empty() || username.is_empty() {
+        return Err(DockerError::InvalidInput {
+            message: "Registry and username are required".to_string(),
+        });
+    }
+
+    let (key, secret) = (registry.clone(), password.to_string());
+    if let Err(e) = with_keychain(move || keychain_entry(&key)?.set_password(&secret)).await {
+        log_docker_operation("save_registry_login", false, Some(&e.to_string()));
+        return Err(e);
+    }
+
+    let mut config = load_config()?;
+    config.registries.retain(|c| c.registry != registry);
+    config.registries.push(RegistryCredential {
+        registry: registry.clone(),
+        username: username.to_string(),
+        password: None,
+    });
+    save_config(&config)?;
+
+    log_docker_operation(
+        "save_registry_login",
+        true,
+        Some(&format!("Stored credentials for {registry}")),
+    );
+    Ok(())
+}
+
+pub async fn delete_registry_login(registry: &str) -> Result<()> {
+    let registry = normalize_registry(registry);
+    let mut config = load_config()?;
+    let before = config.registries.len();
+    config.registries.retain(|c| c.registry != registry);
+    if config.registries.len() == before {
+        return Err(DockerError::InvalidInput {
+            message: format!("No stored credentials for {registry}"),
+        });
+    }
+
+    let key = registry.clone();
+    with_keychain(move || match keychain_entry(&key)?.delete_credential() {
+        Err(keyring::Error::NoEntry) => Ok(()),
+        result => result,
+    })
+    .await?;
+    save_config(&config)
+}
+
+/// Moves passwords that earlier versions kept in the settings file into the
+/// keychain. The file is only rewritten once every secret has been stored,
+/// so nothing is lost when the keychain is unavailable.
+pub fn migrate_registry_passwords() {
+    let Ok(mut config) = load_config() else {
+        return;
+    };
+    if config.registries.iter().all(|c| c.password.is_none()) {
+        return;
+    }
+    for credential in &mut config.registries {
+        let Some(password) = credential.password.take() else {
+            continue;
+        };
+        if let Err(e) =
+            keychain_entry(&credential.registry).and_then(|entry| entry.set_password(&password))
+        {
+            warn!(
+                "Could not move the {} password to the system keychain: {e}",
+                credential.registry
+            );
+            return;
+        }
+    }
+    if let Err(e) = save_config(&config) {
+        warn!("Could not remove migrated registry passwords from the settings: {e}");
+    }
+}
+
+fn keychain_entry(registry: &str) -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, registry)
+}
+
+/// Runs a keychain call on the blocking pool; the backends wait on the OS
+/// (D-Bus on Linux) and must not be driven from the async runtime.
+async fn with_keychain<T: Send + 'static>(
+    call: impl FnOnce() -> keyring::Result<T> + Send + 'static,
+) -> Result<T> {
+    tauri::async_runtime::spawn_blocking(call)
+        .await
+        .map_err(|e| DockerError::OperationFailed {
+            message: e.to_string(),
+        })?
+        .map_err(|e| DockerError::OperationFailed {
+            message: format!("System keychain: {e}"),
+        })
+}
+
+fn server_address(registry: &str) -> String {
+    if registry == DOCKER_HUB_REGISTRY {
+        DOCKER_HUB_SERVER.to_string()
+    } else {
+        registry.to_string()
+    }
+}
+
+fn read_docker_cli_config() -> DockerCliConfig {
+    let path = std::env::var("DOCKER_CONFIG")
+        .map(std::path::PathBuf::from)
+        .ok()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".docker")))
+        .map(|dir| dir.join("config.json"));
+
+    path.and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+// `auth` is base64("username:password")
+fn decode_auth(encoded: &str) -> Option<(String, String)> {
+    let decoded = BASE64.decode(encoded.trim()).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
+}
+
+/// Runs `docker-credential-<helper> <action>` with `input` on stdin
+async fn run_credential_helper(helper: &str, action: &str, input: &str) -> Option<String> {
+    let program = format!("docker-credential-{helper}");
+    let mut child = Command::new(&program)
+        .arg(action)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| warn!("Failed to run {}: {}", program, e))
+        .ok()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes()).await;
+    }
+
+    let output = child.wait_with_output().await.ok()?;
+    if !output.status.success() {
+        warn!(
+            "{} {} failed: {}",
+            program,
+            action,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
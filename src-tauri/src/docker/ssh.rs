@@ -0,0 +1,282 @@
+use crate::utils::{log_docker_operation, DockerError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+pub const DEFAULT_REMOTE_SOCKET: &str = "/var/run/docker.sock";
+
+const TUNNEL_READY_TIMEOUT: Duration = Duration::from_secs(15);
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+const PORT_ATTEMPTS: u32 = 3;
+const STDERR_TAIL_LINES: usize = 20;
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// What a local tunnel forwards to
+#[derive(Debug, Clone)]
+pub struct SshTunnelSpec {
+    pub destination: String, // `user@host` or `ssh://user@host:port`
+    pub remote_socket: String,
+    pub identity_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshTunnelStatus {
+    pub state: String, // "up" or "reconnecting"
+    pub destination: String,
+    pub local_port: u16,
+    pub reconnects: u32,
+    pub last_error: Option<String>,
+}
+
+struct SshTunnel {
+    status: Arc<Mutex<SshTunnelStatus>>,
+    supervisor: JoinHandle<()>,
+}
+
+/// A running ssh process. Its stderr is drained continuously so a chatty
+/// tunnel can't fill the pipe and block; the last lines are kept for errors.
+struct TunnelProcess {
+    child: Child,
+    stderr: Arc<std::sync::Mutex<VecDeque<String>>>,
+    drain: Option<JoinHandle<()>>,
+}
+
+impl TunnelProcess {
+    /// What ssh last wrote to stderr, once it has all been read
+    async fn stderr_tail(&mut self) -> String {
+        if let Some(drain) = self.drain.take() {
+            let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, drain).await;
+        }
+        let lines = self.stderr.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// SSH tunnels of connected hosts, keyed by host id. Each tunnel is a system
+/// `ssh` process forwarding a local TCP port to the remote Docker socket; a
+/// supervisor task restarts it on the same port whenever it drops, so the
+/// bollard client pointing at that port keeps working.
+pub struct SshTunnelRegistry {
+    tunnels: Mutex<HashMap<String, SshTunnel>>,
+}
+
+impl SshTunnelRegistry {
+    pub fn new() -> Self {
+        Self {
+            tunnels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Opens a tunnel for `host_id` and returns the local port it listens on.
+    /// An existing tunnel for the same host is replaced.
+    pub async fn open(&self, host_id: &str, spec: SshTunnelSpec) -> Result<u16> {
+        self.close(host_id).await;
+
+        // Another process may take the probed port before ssh binds it
+        let mut attempt = 1;
+        let (local_port, process) = loop {
+            let local_port = free_local_port().await?;
+            match spawn_tunnel(&spec, local_port).await {
+                Ok(process) => break (local_port, process),
+                Err(e) if attempt < PORT_ATTEMPTS && e.to_string().contains("already in use") => {
+                    warn!("Local port {} was taken, retrying: {}", local_port, e);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        let status = Arc::new(Mutex::new(SshTunnelStatus {
+            state: "up".to_string(),
+            destination: spec.destination.clone(),
+            local_port,
+            reconnects: 0,
+            last_error: None,
+        }));
+
+        let supervisor = tauri::async_runtime::spawn(supervise_tunnel(
+            host_id.to_string(),
+            spec,
+            local_port,
+            process,
+            Arc::clone(&status),
+        ));
+
+        self.tunnels
+            .lock()
+            .await
+            .insert(host_id.to_string(), SshTunnel { status, supervisor });
+
+        log_docker_operation(
+            "open_ssh_tunnel",
+            true,
+            Some(&format!(
+                "Tunnel for host '{host_id}' listening on 127.0.0.1:{local_port}"
+            )),
+        );
+        Ok(local_port)
+    }
+
+    /// Stops a host's tunnel; the ssh process is killed along with its supervisor
+    pub async fn close(&self, host_id: &str) {
+        if let Some(tunnel) = self.tunnels.lock().await.remove(host_id) {
+            tunnel.supervisor.abort();
+            log_docker_operation(
+                "close_ssh_tunnel",
+                true,
+                Some(&format!("Closed tunnel for host '{host_id}'")),
+            );
+        }
+    }
+
+    pub async fn status(&self, host_id: &str) -> Option<SshTunnelStatus> {
+        let status = {
+            let tunnels = self.tunnels.lock().await;
+            Arc::clone(&tunnels.get(host_id)?.status)
+        };
+        let status = status.lock().await.clone();
+        Some(status)
+    }
+}
+
+impl Default for SshTunnelRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Global SSH tunnel registry
+lazy_static::lazy_static! {
+    pub static ref SSH_TUNNELS: SshTunnelRegistry = SshTunnelRegistry::new();
+}
+
+/// Waits for the ssh process to exit and restarts it with exponential
+/// backoff until the tunnel is closed.
+async fn supervise_tunnel(
+    host_id: String,
+    spec: SshTunnelSpec,
+    local_port: u16,
+    mut process: TunnelProcess,
+    status: Arc<Mutex<SshTunnelStatus>>,
+) {
+    loop {
+        let exit = process.child.wait().await;
+        let stderr = process.stderr_tail().await;
+        let reason = match exit {
+            Ok(code) if stderr.is_empty() => format!("ssh exited with {code}"),
+            Ok(code) => format!("ssh exited with {code}: {stderr}"),
+            Err(e) => format!("ssh failed: {e}"),
+        };
+        warn!("SSH tunnel for '{}' dropped: {}", host_id, reason);
+        {
+            let mut status = status.lock().await;
+            status.state = "reconnecting".to_string();
+            status.last_error = Some(reason);
+        }
+
+        let mut delay = RECONNECT_INITIAL_DELAY;
+        process = loop {
+            tokio::time::sleep(delay).await;
+            match spawn_tunnel(&spec, local_port).await {
+                Ok(process) => break process,
+                Err(e) => {
+                    warn!("Reconnecting SSH tunnel for '{}' failed: {}", host_id, e);
+                    status.lock().await.last_error = Some(e.to_string());
+                    delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                }
+            }
+        };
+
+        info!("SSH tunnel for '{}' re-established", host_id);
+        let mut status = status.lock().await;
+        status.state = "up".to_string();
+        status.reconnects += 1;
+    }
+}
+
+/// Starts `ssh -L` and waits until the forwarded port accepts connections
+async fn spawn_tunnel(spec: &SshTunnelSpec, local_port: u16) -> Result<TunnelProcess> {
+    let mut command = Command::new("ssh");
+    command
+        .arg("-N")
+        .args(["-o", "BatchMode=yes"])
+        .args(["-o", "LogLevel=ERROR"])
+        .args(["-o", "ExitOnForwardFailure=yes"])
+        .args(["-o", "ServerAliveInterval=15"])
+        .args(["-o", "ServerAliveCountMax=3"]);
+    if let Some(identity_file) = &spec.identity_file {
+        command.args(["-i", identity_file]);
+    }
+    command
+        .arg("-L")
+        .arg(format!("127.0.0.1:{local_port}:{}", spec.remote_socket))
+        // Nothing past here is read as an option, whatever the destination holds
+        .arg("--")
+        .arg(&spec.destination)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = command.spawn().map_err(|e| DockerError::SshTunnel {
+        message: format!("Failed to start ssh: {e}"),
+    })?;
+
+    let stderr = Arc::new(std::sync::Mutex::new(VecDeque::new()));
+    let pipe = child.stderr.take();
+    let tail = Arc::clone(&stderr);
+    let drain = tauri::async_runtime::spawn(async move {
+        let Some(pipe) = pipe else {
+            return;
+        };
+        let mut lines = BufReader::new(pipe).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let mut tail = tail.lock().unwrap_or_else(|e| e.into_inner());
+            if tail.len() == STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+    });
+    let mut process = TunnelProcess {
+        child,
+        stderr,
+        drain: Some(drain),
+    };
+
+    let deadline = tokio::time::Instant::now() + TUNNEL_READY_TIMEOUT;
+    loop {
+        if let Some(exit) = process.child.try_wait()? {
+            return Err(DockerError::SshTunnel {
+                message: format!(
+                    "ssh to {} exited with {exit}: {}",
+                    spec.destination,
+                    process.stderr_tail().await
+                ),
+            });
+        }
+        if TcpStream::connect(("127.0.0.1", local_port)).await.is_ok() {
+            return Ok(process);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(DockerError::SshTunnel {
+                message: format!("Timed out waiting for the tunnel to {}", spec.destination),
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+async fn free_local_port() -> Result<u16> {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    Ok(listener.local_addr()?.port())
+}
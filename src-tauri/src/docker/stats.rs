@@ -1,7 +1,275 @@
-// TODO: Stats operations
-use crate::utils::error::DockerResult;
+use crate::docker::client::DOCKER_CLIENT;
+use crate::docker::subscriptions::SUBSCRIPTIONS;
+use crate::utils::{log_docker_operation, DockerError, Result};
+use bollard::models::{ContainerBlkioStats, ContainerCpuUsage, ContainerStatsResponse};
+use bollard::query_parameters::{InspectContainerOptions, StatsOptions};
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
 
-pub async fn get_docker_stats() -> DockerResult<()> {
-    // Stats operations implementation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerResourceStats {
+    pub id: String,
+    pub name: String,
+    pub read: String,
+    pub cpu_percent: f64,
+    pub online_cpus: u64,
+    pub memory_usage: u64, // usage minus page cache, as reported by `docker stats`
+    pub memory_limit: u64,
+    pub memory_percent: f64,
+    pub network_rx_bytes: u64,
+    pub network_tx_bytes: u64,
+    pub network_rx_per_sec: f64,
+    pub network_tx_per_sec: f64,
+    pub block_read_bytes: u64,
+    pub block_write_bytes: u64,
+    pub block_read_per_sec: f64,
+    pub block_write_per_sec: f64,
+    pub pids: u64,
+}
+
+// Cumulative counters from the previous sample, used to derive per-second rates
+struct StatsSample {
+    read: Option<chrono::DateTime<chrono::FixedOffset>>,
+    network_rx_bytes: u64,
+    network_tx_bytes: u64,
+    block_read_bytes: u64,
+    block_write_bytes: u64,
+}
+
+/// Streams live stats for a container, emitting a computed
+/// `ContainerResourceStats` as `container-stats:{subscription_id}` for every
+/// sample the daemon produces (roughly once per second). A final
+/// `container-stats-end:{subscription_id}` event is emitted when the stream
+/// closes.
+pub async fn subscribe_container_stats(
+    app: AppHandle,
+    host: Option<&str>,
+    id: &str,
+) -> Result<String> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    if let Err(e) = client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await
+    {
+        log_docker_operation("subscribe_container_stats", false, Some(&e.to_string()));
+        return if e.to_string().contains("404") {
+            Err(DockerError::ContainerNotFound { id: id.to_string() })
+        } else {
+            Err(DockerError::Connection(e))
+        };
+    }
+
+    let host_id = DOCKER_CLIENT.host_id(host).await.unwrap_or_default();
+    let options = Some(StatsOptions {
+        stream: true,
+        one_shot: false,
+    });
+    let mut stream = client.stats(id, options.clone());
+
+    let container_id = id.to_string();
+    let subscription_id = SUBSCRIPTIONS
+        .spawn("stats", |subscription_id| async move {
+            let stats_event = format!("container-stats:{subscription_id}");
+            let mut client = client;
+            let mut previous: Option<StatsSample> = None;
+            let mut error = None;
+
+            loop {
+                match stream.next().await {
+                    Some(Ok(stats)) => {
+                        let (computed, sample) = compute_resource_stats(stats, previous.as_ref());
+                        previous = Some(sample);
+                        if let Err(e) = app.emit(&stats_event, computed) {
+                            warn!("Failed to emit stats for {}: {}", subscription_id, e);
+                            break;
+                        }
+                    }
+                    // A dropped connection can also just end the stream
+                    ended => {
+                        let failure = ended.and_then(|item| item.err()).map(|e| e.to_string());
+                        match DOCKER_CLIENT.wait_for_reconnect(&host_id, &client).await {
+                            Some(restored) => {
+                                info!("Resuming stats subscription {}", subscription_id);
+                                client = restored;
+                                stream = client.stats(&container_id, options.clone());
+                                previous = None;
+                            }
+                            None => {
+                                error = failure;
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let _ = app.emit(&format!("container-stats-end:{subscription_id}"), error);
+        })
+        .await;
+
+    log_docker_operation(
+        "subscribe_container_stats",
+        true,
+        Some(&format!(
+            "Streaming stats for container {id} as {subscription_id}"
+        )),
+    );
+    Ok(subscription_id)
+}
+
+pub async fn unsubscribe_container_stats(subscription_id: &str) -> Result<()> {
+    let cancelled = SUBSCRIPTIONS.cancel(subscription_id).await;
+    log_docker_operation(
+        "unsubscribe_container_stats",
+        true,
+        Some(&if cancelled {
+            format!("Stopped streaming stats for {subscription_id}")
+        } else {
+            format!("Stats subscription {subscription_id} had already ended")
+        }),
+    );
     Ok(())
 }
+
+fn compute_resource_stats(
+    stats: ContainerStatsResponse,
+    previous: Option<&StatsSample>,
+) -> (ContainerResourceStats, StatsSample) {
+    // CPU: same formula as the docker CLI, scaled by the number of online CPUs
+    let cpu_stats = stats.cpu_stats.unwrap_or_default();
+    let precpu_stats = stats.precpu_stats.unwrap_or_default();
+    let cpu_usage = cpu_stats.cpu_usage.unwrap_or_default();
+    let total_usage = |usage: Option<ContainerCpuUsage>| {
+        usage
+            .and_then(|usage| usage.total_usage)
+            .unwrap_or_default()
+    };
+    let cpu_delta = cpu_usage
+        .total_usage
+        .unwrap_or_default()
+        .saturating_sub(total_usage(precpu_stats.cpu_usage));
+    let system_delta = cpu_stats
+        .system_cpu_usage
+        .unwrap_or_default()
+        .saturating_sub(precpu_stats.system_cpu_usage.unwrap_or_default());
+    let online_cpus = cpu_stats.online_cpus.map(u64::from).unwrap_or_else(|| {
+        cpu_usage
+            .percpu_usage
+            .as_ref()
+            .map(|percpu| percpu.len() as u64)
+            .unwrap_or(1)
+    });
+    let cpu_percent = if cpu_delta > 0 && system_delta > 0 {
+        cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    // Memory: exclude inactive page cache (cgroup v1 and v2 report it differently)
+    let memory_stats = stats.memory_stats.unwrap_or_default();
+    let usage = memory_stats.usage.unwrap_or_default();
+    let cache = memory_stats
+        .stats
+        .as_ref()
+        .and_then(|stats| {
+            stats
+                .get("total_inactive_file")
+                .or_else(|| stats.get("inactive_file"))
+        })
+        .copied()
+        .unwrap_or_default();
+    let memory_usage = if cache < usage { usage - cache } else { usage };
+    let memory_limit = memory_stats.limit.unwrap_or_default();
+    let memory_percent = if memory_limit > 0 {
+        memory_usage as f64 / memory_limit as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    let (network_rx_bytes, network_tx_bytes) = stats
+        .networks
+        .as_ref()
+        .map(|networks| {
+            networks.values().fold((0, 0), |(rx, tx), network| {
+                (
+                    rx + network.rx_bytes.unwrap_or_default(),
+                    tx + network.tx_bytes.unwrap_or_default(),
+                )
+            })
+        })
+        .unwrap_or_default();
+
+    let (block_read_bytes, block_write_bytes) =
+        sum_blkio_bytes(&stats.blkio_stats.unwrap_or_default());
+
+    let read = stats
+        .read
+        .as_deref()
+        .and_then(|read| chrono::DateTime::parse_from_rfc3339(read).ok());
+    let elapsed_secs = previous
+        .and_then(|prev| Some((read? - prev.read?).num_milliseconds() as f64 / 1000.0))
+        .filter(|secs| *secs > 0.0);
+    let per_sec = |current: u64, previous: Option<u64>| match (elapsed_secs, previous) {
+        (Some(secs), Some(previous)) => current.saturating_sub(previous) as f64 / secs,
+        _ => 0.0,
+    };
+
+    let computed = ContainerResourceStats {
+        id: stats.id.unwrap_or_default(),
+        name: stats.name.unwrap_or_default(),
+        cpu_percent,
+        online_cpus,
+        memory_usage,
+        memory_limit,
+        memory_percent,
+        network_rx_bytes,
+        network_tx_bytes,
+        network_rx_per_sec: per_sec(network_rx_bytes, previous.map(|p| p.network_rx_bytes)),
+        network_tx_per_sec: per_sec(network_tx_bytes, previous.map(|p| p.network_tx_bytes)),
+        block_read_bytes,
+        block_write_bytes,
+        block_read_per_sec: per_sec(block_read_bytes, previous.map(|p| p.block_read_bytes)),
+        block_write_per_sec: per_sec(block_write_bytes, previous.map(|p| p.block_write_bytes)),
+        pids: stats
+            .pids_stats
+            .and_then(|pids| pids.current)
+            .unwrap_or_default(),
+        read: stats.read.unwrap_or_default(),
+    };
+
+    let sample = StatsSample {
+        read,
+        network_rx_bytes,
+        network_tx_bytes,
+        block_read_bytes,
+        block_write_bytes,
+    };
+
+    (computed, sample)
+}
+
+fn sum_blkio_bytes(blkio_stats: &ContainerBlkioStats) -> (u64, u64) {
+    blkio_stats
+        .io_service_bytes_recursive
+        .as_ref()
+        .map(|entries| {
+            entries.iter().fold((0, 0), |(read, write), entry| {
+                let value = entry.value.unwrap_or_default();
+                match entry
+                    .op
+                    .as_deref()
+                    .unwrap_or_default()
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "read" => (read + value, write),
+                    "write" => (read, write + value),
+                    _ => (read, write),
+                }
+            })
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// Registry of background streaming tasks (log follows, stats, ...) that the
+/// frontend subscribes to and later cancels by subscription id.
+pub struct SubscriptionRegistry {
+    tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+    next_id: AtomicU64,
+}
+
+impl SubscriptionRegistry {
+    pub fn new() -> Self {
+        Self {
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Spawns `task` under a fresh subscription id of the form `{kind}-{n}`.
+    /// The task receives its own id (used to key emitted events) and is
+    /// dropped from the registry once it finishes on its own.
+    pub async fn spawn<F, Fut>(&self, kind: &str, task: F) -> String
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let subscription_id = format!("{kind}-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let future = task(subscription_id.clone());
+
+        // Hold the lock across the spawn so a task that finishes immediately
+        // cannot try to remove itself before it has been inserted.
+        let mut tasks = self.tasks.lock().await;
+        let registry = Arc::clone(&self.tasks);
+        let finished_id = subscription_id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            future.await;
+            registry.lock().await.remove(&finished_id);
+            info!("Subscription {} finished", finished_id);
+        });
+        tasks.insert(subscription_id.clone(), handle);
+
+        info!("Subscription {} started", subscription_id);
+        subscription_id
+    }
+
+    /// Aborts a running subscription. Returns `false` if it had already ended.
+    pub async fn cancel(&self, subscription_id: &str) -> bool {
+        match self.tasks.lock().await.remove(subscription_id) {
+            Some(handle) => {
+                handle.abort();
+                info!("Subscription {} cancelled", subscription_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for SubscriptionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Global subscription registry
+lazy_static::lazy_static! {
+    pub static ref SUBSCRIPTIONS: SubscriptionRegistry = SubscriptionRegistry::new();
+}
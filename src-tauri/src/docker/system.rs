@@ -1,7 +1,11 @@
 use crate::docker::client::DOCKER_CLIENT;
+use crate::docker::ssh::{SshTunnelStatus, SSH_TUNNELS};
 use crate::utils::{log_docker_operation, DockerError, Result};
-use bollard::models::SystemInfo as BollardSystemInfo;
-use bollard::system::Version;
+use bollard::models::{
+    ContainerSummaryStateEnum, SystemDataUsageResponse, SystemInfo as BollardSystemInfo,
+    SystemVersion,
+};
+use bollard::query_parameters::{DataUsageOptions, ListNetworksOptions, ListVolumesOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -109,7 +113,6 @@ pub struct DockerSwarmManager {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerCommit {
     pub id: String,
-    pub expected: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,6 +127,8 @@ pub struct DockerConnectionStatus {
     pub error: Option<String>,
     pub version: Option<String>,
     pub api_version: Option<String>,
+    pub profile: Option<String>, // host id, i.e. the connection profile in use
+    pub tunnel: Option<SshTunnelStatus>, // set for hosts reached over SSH
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,8 +142,39 @@ pub struct DockerStats {
     pub networks_total: i64,
 }
 
-pub async fn get_docker_version() -> Result<DockerVersion> {
-    let client = DOCKER_CLIENT.get_client().await?;
+/// One row of `docker system df`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskUsageCategory {
+    pub total_count: usize,
+    pub active_count: usize, // used by a container, or a running container
+    pub size: u64,
+    pub reclaimable: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsageItem {
+    pub category: String, // "image", "container", "volume" or "build-cache"
+    pub id: String,
+    pub name: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsage {
+    pub images: DiskUsageCategory,
+    pub containers: DiskUsageCategory,
+    pub volumes: DiskUsageCategory,
+    pub build_cache: DiskUsageCategory,
+    pub total_size: u64,
+    pub reclaimable: u64,
+    pub top_consumers: Vec<DiskUsageItem>, // largest first
+}
+
+// How many of the largest items `get_disk_usage` reports
+const TOP_CONSUMERS: usize = 10;
+
+pub async fn get_docker_version(host: Option<&str>) -> Result<DockerVersion> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     match client.version().await {
         Ok(version) => {
@@ -157,8 +193,8 @@ pub async fn get_docker_version() -> Result<DockerVersion> {
     }
 }
 
-pub async fn get_docker_info() -> Result<DockerInfo> {
-    let client = DOCKER_CLIENT.get_client().await?;
+pub async fn get_docker_info(host: Option<&str>) -> Result<DockerInfo> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
 
     match client.info().await {
         Ok(info) => {
@@ -180,22 +216,29 @@ pub async fn get_docker_info() -> Result<DockerInfo> {
     }
 }
 
-pub async fn get_docker_system_info() -> Result<DockerSystemInfo> {
-    let version = get_docker_version().await?;
-    let info = get_docker_info().await?;
+pub async fn get_docker_system_info(host: Option<&str>) -> Result<DockerSystemInfo> {
+    let version = get_docker_version(host).await?;
+    let info = get_docker_info(host).await?;
 
     Ok(DockerSystemInfo { version, info })
 }
 
-pub async fn get_connection_status() -> DockerConnectionStatus {
-    match DOCKER_CLIENT.test_connection().await {
+pub async fn get_connection_status(host: Option<&str>) -> DockerConnectionStatus {
+    let profile = DOCKER_CLIENT.host_id(host).await;
+    let tunnel = match &profile {
+        Some(host_id) => SSH_TUNNELS.status(host_id).await,
+        None => None,
+    };
+    match DOCKER_CLIENT.test_connection(host).await {
         Ok(true) => {
-            if let Ok(version) = get_docker_version().await {
+            if let Ok(version) = get_docker_version(host).await {
                 DockerConnectionStatus {
                     connected: true,
                     error: None,
                     version: Some(version.version),
                     api_version: Some(version.api_version),
+                    profile,
+                    tunnel,
                 }
             } else {
                 DockerConnectionStatus {
@@ -203,6 +246,8 @@ pub async fn get_connection_status() -> DockerConnectionStatus {
                     error: None,
                     version: None,
                     api_version: None,
+                    profile,
+                    tunnel,
                 }
             }
         }
@@ -211,33 +256,184 @@ pub async fn get_connection_status() -> DockerConnectionStatus {
             error: Some("Docker daemon not connected".to_string()),
             version: None,
             api_version: None,
+            profile,
+            tunnel,
         },
         Err(e) => DockerConnectionStatus {
             connected: false,
             error: Some(e.to_string()),
             version: None,
             api_version: None,
+            profile,
+            tunnel,
         },
     }
 }
 
-pub async fn get_docker_stats() -> Result<DockerStats> {
-    let info = get_docker_info().await?;
+pub async fn get_docker_stats(host: Option<&str>) -> Result<DockerStats> {
+    let info = get_docker_info(host).await?;
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    let volumes = client
+        .list_volumes(None::<ListVolumesOptions>)
+        .await
+        .map_err(DockerError::Connection)?;
+    let networks = client
+        .list_networks(None::<ListNetworksOptions>)
+        .await
+        .map_err(DockerError::Connection)?;
 
-    // Calculate volumes and networks count (simplified for now)
-    // In a real implementation, you'd call list_volumes and list_networks
     Ok(DockerStats {
         containers_total: info.containers,
         containers_running: info.containers_running,
         containers_stopped: info.containers_stopped,
         containers_paused: info.containers_paused,
         images_total: info.images,
-        volumes_total: 0,  // TODO: Implement volumes count
-        networks_total: 0, // TODO: Implement networks count
+        volumes_total: volumes.volumes.map_or(0, |volumes| volumes.len() as i64),
+        networks_total: networks.len() as i64,
     })
 }
 
-fn convert_version(version: Version) -> DockerVersion {
+/// Disk usage per object type, like `docker system df -v`
+pub async fn get_docker_disk_usage(host: Option<&str>) -> Result<DiskUsage> {
+    let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+    match client.df(None::<DataUsageOptions>).await {
+        Ok(usage) => {
+            let result = convert_disk_usage(usage);
+            log_docker_operation(
+                "get_docker_disk_usage",
+                true,
+                Some(&format!(
+                    "Using {} bytes, {} reclaimable",
+                    result.total_size, result.reclaimable
+                )),
+            );
+            Ok(result)
+        }
+        Err(e) => {
+            log_docker_operation("get_docker_disk_usage", false, Some(&e.to_string()));
+            Err(DockerError::Connection(e))
+        }
+    }
+}
+
+/// Sums up `/system/df` the way the docker CLI does: image size counts shared
+/// layers once, and only unused objects are reclaimable.
+fn convert_disk_usage(usage: SystemDataUsageResponse) -> DiskUsage {
+    let size = |size: Option<i64>| size.unwrap_or_default().max(0) as u64;
+    let mut items = Vec::new();
+
+    let mut images = DiskUsageCategory::default();
+    let mut images_used = 0;
+    for image in usage.images.unwrap_or_default() {
+        images.total_count += 1;
+        if image.containers > 0 {
+            images.active_count += 1;
+            if image.shared_size >= 0 {
+                images_used += (image.size - image.shared_size).max(0) as u64;
+            }
+        }
+        items.push(DiskUsageItem {
+            category: "image".to_string(),
+            name: image
+                .repo_tags
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "<none>".to_string()),
+            id: image.id,
+            size: image.size.max(0) as u64,
+        });
+    }
+    images.size = size(usage.layers_size);
+    images.reclaimable = images.size.saturating_sub(images_used);
+
+    let mut containers = DiskUsageCategory::default();
+    for container in usage.containers.unwrap_or_default() {
+        let size_rw = size(container.size_rw);
+        containers.total_count += 1;
+        containers.size += size_rw;
+        if container.state == Some(ContainerSummaryStateEnum::RUNNING) {
+            containers.active_count += 1;
+        } else {
+            containers.reclaimable += size_rw;
+        }
+        items.push(DiskUsageItem {
+            category: "container".to_string(),
+            id: container.id.unwrap_or_default(),
+            name: container
+                .names
+                .and_then(|names| names.first().cloned())
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+            size: size_rw,
+        });
+    }
+
+    let mut volumes = DiskUsageCategory::default();
+    for volume in usage.volumes.unwrap_or_default() {
+        let (volume_size, ref_count) = volume
+            .usage_data
+            .map(|data| (data.size.max(0) as u64, data.ref_count))
+            .unwrap_or_default();
+        volumes.total_count += 1;
+        volumes.size += volume_size;
+        if ref_count > 0 {
+            volumes.active_count += 1;
+        } else {
+            volumes.reclaimable += volume_size;
+        }
+        items.push(DiskUsageItem {
+            category: "volume".to_string(),
+            id: volume.name.clone(),
+            name: volume.name,
+            size: volume_size,
+        });
+    }
+
+    let mut build_cache = DiskUsageCategory::default();
+    for record in usage.build_cache.unwrap_or_default() {
+        let in_use = record.in_use.unwrap_or_default();
+        build_cache.total_count += 1;
+        if in_use {
+            build_cache.active_count += 1;
+        }
+        // Shared records are already counted with the images using them
+        if record.shared.unwrap_or_default() {
+            continue;
+        }
+        let record_size = size(record.size);
+        build_cache.size += record_size;
+        if !in_use {
+            build_cache.reclaimable += record_size;
+        }
+        items.push(DiskUsageItem {
+            category: "build-cache".to_string(),
+            name: record.description.unwrap_or_default(),
+            id: record.id.unwrap_or_default(),
+            size: record_size,
+        });
+    }
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.size));
+    items.truncate(TOP_CONSUMERS);
+
+    DiskUsage {
+        total_size: images.size + containers.size + volumes.size + build_cache.size,
+        reclaimable: images.reclaimable
+            + containers.reclaimable
+            + volumes.reclaimable
+            + build_cache.reclaimable,
+        images,
+        containers,
+        volumes,
+        build_cache,
+        top_consumers: items,
+    }
+}
+
+fn convert_version(version: SystemVersion) -> DockerVersion {
     DockerVersion {
         version: version.version.unwrap_or_default(),
         api_version: version.api_version.unwrap_or_default(),
@@ -385,11 +581,6 @@ fn convert_info(info: BollardSystemInfo) -> DockerInfo {
                 .as_ref()
                 .and_then(|c| c.id.clone())
                 .unwrap_or_default(),
-            expected: info
-                .containerd_commit
-                .as_ref()
-                .and_then(|c| c.expected.clone())
-                .unwrap_or_default(),
         },
         runc_commit: DockerCommit {
             id: info
@@ -397,11 +588,6 @@ fn convert_info(info: BollardSystemInfo) -> DockerInfo {
                 .as_ref()
                 .and_then(|c| c.id.clone())
                 .unwrap_or_default(),
-            expected: info
-                .runc_commit
-                .as_ref()
-                .and_then(|c| c.expected.clone())
-                .unwrap_or_default(),
         },
         init_commit: DockerCommit {
             id: info
@@ -409,11 +595,6 @@ fn convert_info(info: BollardSystemInfo) -> DockerInfo {
                 .as_ref()
                 .and_then(|c| c.id.clone())
                 .unwrap_or_default(),
-            expected: info
-                .init_commit
-                .as_ref()
-                .and_then(|c| c.expected.clone())
-                .unwrap_or_default(),
         },
         security_options: info.security_options.unwrap_or_default(),
     }
@@ -1,5 +1,7 @@
 mod commands;
 mod docker;
+mod models;
+mod terminal;
 mod utils;
 
 use commands::*;
@@ -9,6 +11,7 @@ use utils::init_logger;
 pub fn run() {
     // Initialize logging
     init_logger();
+    docker::migrate_registry_passwords();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -16,26 +19,71 @@ pub fn run() {
             // System commands
             connect_docker,
             disconnect_docker,
+            get_connected_hosts,
             get_docker_connection_status,
             get_system_info,
             get_system_stats,
+            get_disk_usage,
             test_docker_connection,
+            get_connection_profiles,
+            add_connection_profile,
+            remove_connection_profile,
+            switch_connection_profile,
             // Container commands
             get_containers,
             get_container_details,
+            create_container_cmd,
             start_container_cmd,
             stop_container_cmd,
             restart_container_cmd,
             remove_container_cmd,
             kill_container_cmd,
+            pause_container_cmd,
+            unpause_container_cmd,
+            bulk_container_action_cmd,
+            update_container_resources_cmd,
+            recreate_container_cmd,
+            export_container_definition_cmd,
             get_container_stats_cmd,
+            subscribe_container_stats_cmd,
+            unsubscribe_container_stats_cmd,
             get_container_logs_cmd,
+            subscribe_container_logs_cmd,
+            unsubscribe_container_logs_cmd,
             exec_container_cmd,
+            // Terminal commands
+            open_exec_session_cmd,
+            write_exec_stdin_cmd,
+            resize_exec_session_cmd,
+            close_exec_session_cmd,
+            // Compose commands
+            get_compose_projects,
+            start_compose_project_cmd,
+            stop_compose_project_cmd,
+            restart_compose_project_cmd,
+            remove_compose_project_cmd,
+            compose_up_cmd,
             // Image commands
             get_images,
             get_image_details,
+            get_image_history,
             remove_image_cmd,
+            tag_image_cmd,
+            untag_image_cmd,
+            retag_images_cmd,
+            prune_images_cmd,
             pull_image_cmd,
+            subscribe_image_pull_cmd,
+            cancel_image_pull_cmd,
+            push_image_cmd,
+            build_image_cmd,
+            cancel_image_build_cmd,
+            save_images_cmd,
+            load_images_cmd,
+            cancel_image_archive_cmd,
+            get_registry_logins,
+            add_registry_login,
+            remove_registry_login,
             // Network commands
             get_networks,
             get_network_details,
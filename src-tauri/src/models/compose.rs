@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeProject {
+    pub name: String,
+    pub working_dir: Option<String>,
+    pub config_files: Vec<String>,
+    pub services: Vec<ComposeService>,
+    pub containers_total: usize,
+    pub containers_running: usize,
+    pub status: String, // "running", "partial" or "stopped"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeService {
+    pub name: String,
+    pub containers: Vec<ComposeContainer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeContainer {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub state: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeUpResult {
+    pub project: String,
+    pub networks_created: Vec<String>,
+    pub volumes_created: Vec<String>,
+    pub containers_started: Vec<String>,
+}
+
+/// A parsed `docker-compose.yml`. Only the keys needed to bring a stack up
+/// are modelled; anything else in the file is ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeFile {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub services: BTreeMap<String, ComposeServiceSpec>,
+    #[serde(default)]
+    pub networks: BTreeMap<String, Option<ComposeNetworkSpec>>,
+    #[serde(default)]
+    pub volumes: BTreeMap<String, Option<ComposeVolumeSpec>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeServiceSpec {
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub container_name: Option<String>,
+    #[serde(default)]
+    pub command: Option<ComposeCommand>,
+    #[serde(default)]
+    pub entrypoint: Option<ComposeCommand>,
+    #[serde(default)]
+    pub ports: Vec<ComposePort>,
+    #[serde(default)]
+    pub env_file: ComposeEnvFiles,
+    #[serde(default)]
+    pub environment: ComposeKeyValues,
+    #[serde(default)]
+    pub labels: ComposeKeyValues,
+    #[serde(default)]
+    pub volumes: Vec<ComposeServiceVolume>,
+    #[serde(default)]
+    pub networks: ComposeServiceNetworks,
+    #[serde(default)]
+    pub depends_on: ComposeDependsOn,
+    #[serde(default)]
+    pub restart: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeNetworkSpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub driver: Option<String>,
+    #[serde(default)]
+    pub driver_opts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub external: bool,
+    #[serde(default)]
+    pub labels: ComposeKeyValues,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeVolumeSpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub driver: Option<String>,
+    #[serde(default)]
+    pub driver_opts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub external: bool,
+    #[serde(default)]
+    pub labels: ComposeKeyValues,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeScalar {
+    String(String),
+    Number(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl std::fmt::Display for ComposeScalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComposeScalar::String(s) => write!(f, "{s}"),
+            ComposeScalar::Number(n) => write!(f, "{n}"),
+            ComposeScalar::Float(n) => write!(f, "{n}"),
+            ComposeScalar::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+/// `command`/`entrypoint`: either a shell-style string or an exec-form list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeCommand {
+    Shell(String),
+    Exec(Vec<String>),
+}
+
+/// `environment`/`labels`: either `["KEY=value"]` or `{KEY: value}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeKeyValues {
+    List(Vec<String>),
+    Map(BTreeMap<String, Option<ComposeScalar>>),
+}
+
+impl Default for ComposeKeyValues {
+    fn default() -> Self {
+        ComposeKeyValues::List(Vec::new())
+    }
+}
+
+impl ComposeKeyValues {
+    /// Resolves entries to key/value pairs. Keys without a value are looked
+    /// up in the host environment, as `docker compose` does, and dropped if unset.
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        let from_host = |key: &str| std::env::var(key).ok();
+        match self {
+            ComposeKeyValues::List(entries) => entries
+                .iter()
+                .filter_map(|entry| match entry.split_once('=') {
+                    Some((key, value)) => Some((key.to_string(), value.to_string())),
+                    None => from_host(entry).map(|value| (entry.clone(), value)),
+                })
+                .collect(),
+            ComposeKeyValues::Map(entries) => entries
+                .iter()
+                .filter_map(|(key, value)| match value {
+                    Some(value) => Some((key.clone(), value.to_string())),
+                    None => from_host(key).map(|value| (key.clone(), value)),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `env_file`: a single path or a list of paths, each optionally marked as
+/// not required
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeEnvFiles {
+    Single(String),
+    List(Vec<ComposeEnvFile>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeEnvFile {
+    Path(String),
+    Long {
+        path: String,
+        #[serde(default = "default_required")]
+        required: bool,
+    },
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl Default for ComposeEnvFiles {
+    fn default() -> Self {
+        ComposeEnvFiles::List(Vec::new())
+    }
+}
+
+impl ComposeEnvFiles {
+    /// Paths in the order they are read, each with whether it must exist
+    pub fn to_list(&self) -> Vec<(String, bool)> {
+        match self {
+            ComposeEnvFiles::Single(path) => vec![(path.clone(), true)],
+            ComposeEnvFiles::List(entries) => entries
+                .iter()
+                .map(|entry| match entry {
+                    ComposeEnvFile::Path(path) => (path.clone(), true),
+                    ComposeEnvFile::Long { path, required } => (path.clone(), *required),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `ports`: short syntax (`"8080:80/udp"`, `80`) or long syntax
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposePort {
+    Number(u16),
+    Short(String),
+    Long {
+        target: u16,
+        #[serde(default)]
+        published: Option<ComposeScalar>,
+        #[serde(default)]
+        host_ip: Option<String>,
+        #[serde(default)]
+        protocol: Option<String>,
+    },
+}
+
+/// Service `volumes`: short syntax (`"./data:/data:ro"`) or long syntax
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeServiceVolume {
+    Short(String),
+    Long {
+        #[serde(rename = "type", default)]
+        r#type: Option<String>,
+        #[serde(default)]
+        source: Option<String>,
+        target: String,
+        #[serde(default)]
+        read_only: bool,
+        #[serde(default)]
+        tmpfs: Option<ComposeTmpfsOptions>,
+    },
+}
+
+/// Options of a `type: tmpfs` service volume
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeTmpfsOptions {
+    #[serde(default)]
+    pub size: Option<ComposeScalar>, // bytes, or a string like "64m"
+    #[serde(default)]
+    pub mode: Option<ComposeScalar>,
+}
+
+/// Service `networks`: a list of names or a map with per-network options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeServiceNetworks {
+    List(Vec<String>),
+    Map(BTreeMap<String, Option<ComposeServiceNetwork>>),
+}
+
+impl Default for ComposeServiceNetworks {
+    fn default() -> Self {
+        ComposeServiceNetworks::List(Vec::new())
+    }
+}
+
+impl ComposeServiceNetworks {
+    /// Network keys with their extra aliases, defaulting to the project's `default` network
+    pub fn to_map(&self) -> BTreeMap<String, Vec<String>> {
+        let networks: BTreeMap<String, Vec<String>> = match self {
+            ComposeServiceNetworks::List(names) => {
+                names.iter().map(|n| (n.clone(), Vec::new())).collect()
+            }
+            ComposeServiceNetworks::Map(entries) => entries
+                .iter()
+                .map(|(name, options)| {
+                    let aliases = options
+                        .as_ref()
+                        .map(|o| o.aliases.clone())
+                        .unwrap_or_default();
+                    (name.clone(), aliases)
+                })
+                .collect(),
+        };
+
+        if networks.is_empty() {
+            BTreeMap::from([("default".to_string(), Vec::new())])
+        } else {
+            networks
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeServiceNetwork {
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// `depends_on`: a list of services or a map with start conditions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeDependsOn {
+    List(Vec<String>),
+    Map(BTreeMap<String, ComposeDependency>),
+}
+
+impl Default for ComposeDependsOn {
+    fn default() -> Self {
+        ComposeDependsOn::List(Vec::new())
+    }
+}
+
+impl ComposeDependsOn {
+    pub fn services(&self) -> Vec<String> {
+        match self {
+            ComposeDependsOn::List(services) => services.clone(),
+            ComposeDependsOn::Map(services) => services.keys().cloned().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComposeDependency {
+    #[serde(default)]
+    pub condition: Option<String>,
+}
@@ -1,5 +1,4 @@
 // Models module
-pub mod container;
 pub mod image;
 pub mod network;
 pub mod volume;
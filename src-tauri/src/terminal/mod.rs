@@ -1,3 +1,5 @@
 // Terminal module
 pub mod pty;
 pub mod websocket;
+
+pub use pty::*;
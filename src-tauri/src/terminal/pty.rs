@@ -0,0 +1,309 @@
+use crate::docker::client::DOCKER_CLIENT;
+use crate::utils::{log_docker_operation, DockerError, Result};
+use bollard::container::LogOutput;
+use bollard::exec::{CreateExecOptions, ResizeExecOptions, StartExecOptions, StartExecResults};
+use bollard::Docker;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+type ExecInput = Arc<Mutex<Pin<Box<dyn AsyncWrite + Send>>>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecOutputChunk {
+    pub stream: String, // "stdout", "stderr" or "console"
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecExitStatus {
+    pub exit_code: Option<i64>,
+    pub error: Option<String>,
+}
+
+struct ExecSession {
+    client: Docker, // connection of the host the exec runs on
+    exec_id: String,
+    container_id: String,
+    input: ExecInput,
+    output_task: JoinHandle<()>,
+}
+
+/// Interactive exec sessions keyed by session id. Output is pushed to the
+/// frontend as `exec-output:{session_id}` events and the session is dropped
+/// once the process exits (signalled by an `exec-exit:{session_id}` event).
+pub struct ExecSessionManager {
+    sessions: Arc<Mutex<HashMap<String, ExecSession>>>,
+    next_id: AtomicU64,
+}
+
+impl ExecSessionManager {
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub async fn open(
+        &self,
+        app: AppHandle,
+        host: Option<&str>,
+        container_id: &str,
+        cmd: Vec<String>,
+        tty: bool,
+    ) -> Result<String> {
+        let client = DOCKER_CLIENT.get_host_client(host).await?;
+
+        let exec_options = CreateExecOptions {
+            attach_stdin: Some(true),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            tty: Some(tty),
+            cmd: Some(cmd),
+            ..Default::default()
+        };
+
+        let exec_id = match client.create_exec(container_id, exec_options).await {
+            Ok(created) => created.id,
+            Err(e) => {
+                log_docker_operation("open_exec_session", false, Some(&e.to_string()));
+                return if e.to_string().contains("404") {
+                    Err(DockerError::ContainerNotFound {
+                        id: container_id.to_string(),
+                    })
+                } else {
+                    Err(DockerError::Connection(e))
+                };
+            }
+        };
+
+        let start_options = Some(StartExecOptions {
+            detach: false,
+            tty,
+            ..Default::default()
+        });
+
+        let (mut output, input) = match client.start_exec(&exec_id, start_options).await {
+            Ok(StartExecResults::Attached { output, input }) => (output, input),
+            Ok(StartExecResults::Detached) => {
+                return Err(DockerError::OperationFailed {
+                    message: "Exec session started detached".to_string(),
+                });
+            }
+            Err(e) => {
+                log_docker_operation("open_exec_session", false, Some(&e.to_string()));
+                return Err(DockerError::Connection(e));
+            }
+        };
+
+        let session_id = format!("exec-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        // Hold the lock while spawning so the output task cannot finish and
+        // remove the session before it has been registered.
+        let mut sessions = self.sessions.lock().await;
+        let registry = Arc::clone(&self.sessions);
+        let task_session_id = session_id.clone();
+        let task_exec_id = exec_id.clone();
+        let task_client = client.clone();
+        let output_task = tauri::async_runtime::spawn(async move {
+            let output_event = format!("exec-output:{task_session_id}");
+            let mut decoder = Utf8ChunkDecoder::default();
+            let mut error = None;
+
+            while let Some(item) = output.next().await {
+                match item {
+                    Ok(chunk) => {
+                        let stream = match &chunk {
+                            LogOutput::StdErr { .. } => "stderr",
+                            LogOutput::Console { .. } => "console",
+                            _ => "stdout",
+                        };
+                        let data = decoder.decode(&chunk.into_bytes());
+                        if data.is_empty() {
+                            continue;
+                        }
+                        let payload = ExecOutputChunk {
+                            stream: stream.to_string(),
+                            data,
+                        };
+                        if let Err(e) = app.emit(&output_event, payload) {
+                            warn!("Failed to emit exec output for {}: {}", task_session_id, e);
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                }
+            }
+
+            let exit_code = task_client
+                .inspect_exec(&task_exec_id)
+                .await
+                .ok()
+                .and_then(|inspect| inspect.exit_code);
+            let _ = app.emit(
+                &format!("exec-exit:{task_session_id}"),
+                ExecExitStatus { exit_code, error },
+            );
+
+            registry.lock().await.remove(&task_session_id);
+            info!("Exec session {} exited", task_session_id);
+        });
+
+        sessions.insert(
+            session_id.clone(),
+            ExecSession {
+                client,
+                exec_id,
+                container_id: container_id.to_string(),
+                input: Arc::new(Mutex::new(input)),
+                output_task,
+            },
+        );
+
+        log_docker_operation(
+            "open_exec_session",
+            true,
+            Some(&format!(
+                "Opened exec session {session_id} in container {container_id}"
+            )),
+        );
+        Ok(session_id)
+    }
+
+    pub async fn write_stdin(&self, session_id: &str, data: &str) -> Result<()> {
+        let input = {
+            let sessions = self.sessions.lock().await;
+            let session =
+                sessions
+                    .get(session_id)
+                    .ok_or_else(|| DockerError::ExecSessionNotFound {
+                        id: session_id.to_string(),
+                    })?;
+            Arc::clone(&session.input)
+        };
+
+        let mut input = input.lock().await;
+        input.write_all(data.as_bytes()).await?;
+        input.flush().await?;
+        Ok(())
+    }
+
+    pub async fn resize(&self, session_id: &str, rows: u16, cols: u16) -> Result<()> {
+        let (client, exec_id) = {
+            let sessions = self.sessions.lock().await;
+            sessions
+                .get(session_id)
+                .map(|session| (session.client.clone(), session.exec_id.clone()))
+                .ok_or_else(|| DockerError::ExecSessionNotFound {
+                    id: session_id.to_string(),
+                })?
+        };
+        let options = ResizeExecOptions {
+            height: rows,
+            width: cols,
+        };
+
+        match client.resize_exec(&exec_id, options).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                log_docker_operation("resize_exec_session", false, Some(&e.to_string()));
+                Err(DockerError::Connection(e))
+            }
+        }
+    }
+
+    pub async fn close(&self, session_id: &str) -> Result<()> {
+        match self.sessions.lock().await.remove(session_id) {
+            Some(session) => {
+                // Dropping the input half closes stdin; aborting stops the output pump
+                session.output_task.abort();
+                log_docker_operation(
+                    "close_exec_session",
+                    true,
+                    Some(&format!(
+                        "Closed exec session {session_id} in container {}",
+                        session.container_id
+                    )),
+                );
+                Ok(())
+            }
+            None => Err(DockerError::ExecSessionNotFound {
+                id: session_id.to_string(),
+            }),
+        }
+    }
+}
+
+impl Default for ExecSessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes a byte stream into UTF-8 text, carrying incomplete multi-byte
+/// sequences over to the next chunk instead of mangling them.
+#[derive(Default)]
+struct Utf8ChunkDecoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8ChunkDecoder {
+    fn decode(&mut self, bytes: &[u8]) -> String {
+        self.pending.extend_from_slice(bytes);
+
+        let valid_up_to = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // An incomplete sequence at the very end: keep it for the next chunk
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => {
+                let text = String::from_utf8_lossy(&self.pending).to_string();
+                self.pending.clear();
+                return text;
+            }
+        };
+
+        let rest = self.pending.split_off(valid_up_to);
+        let text = String::from_utf8_lossy(&self.pending).to_string();
+        self.pending = rest;
+        text
+    }
+}
+
+// Global exec session manager
+lazy_static::lazy_static! {
+    pub static ref EXEC_SESSIONS: ExecSessionManager = ExecSessionManager::new();
+}
+
+pub async fn open_exec_session(
+    app: AppHandle,
+    host: Option<&str>,
+    container_id: &str,
+    cmd: Vec<String>,
+    tty: bool,
+) -> Result<String> {
+    EXEC_SESSIONS.open(app, host, container_id, cmd, tty).await
+}
+
+pub async fn write_exec_stdin(session_id: &str, data: &str) -> Result<()> {
+    EXEC_SESSIONS.write_stdin(session_id, data).await
+}
+
+pub async fn resize_exec_session(session_id: &str, rows: u16, cols: u16) -> Result<()> {
+    EXEC_SESSIONS.resize(session_id, rows, cols).await
+}
+
+pub async fn close_exec_session(session_id: &str) -> Result<()> {
+    EXEC_SESSIONS.close(session_id).await
+}
@@ -0,0 +1,422 @@
+use crate::utils::{DockerError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Name of the profile derived from `DOCKER_HOST` / `DOCKER_CONTEXT`. It is
+/// never written to disk and cannot be removed.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+const CONFIG_DIR_NAME: &str = "docsee";
+const CONFIG_FILE_NAME: &str = "config.json";
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+#[cfg(windows)]
+const DEFAULT_SOCKET_PATH: &str = "//./pipe/docker_engine";
+#[cfg(not(windows))]
+const DEFAULT_SOCKET_PATH: &str = "/var/run/docker.sock";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ConnectionEndpoint {
+    Unix {
+        socket_path: String,
+    }, // named pipe path on Windows
+    Tcp {
+        url: String,
+    },
+    Ssh {
+        destination: String, // `user@host` or `ssh://user@host:port`
+        #[serde(default)]
+        remote_socket: Option<String>, // defaults to /var/run/docker.sock
+        #[serde(default)]
+        identity_file: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionProfile {
+    pub name: String,
+    pub endpoint: ConnectionEndpoint,
+    #[serde(default)]
+    pub tls_cert_dir: Option<String>, // holds key.pem, cert.pem and ca.pem, as with DOCKER_CERT_PATH
+    #[serde(default)]
+    pub tls_key: Option<String>, // explicit paths override the files in tls_cert_dir
+    #[serde(default)]
+    pub tls_cert: Option<String>,
+    #[serde(default)]
+    pub tls_ca: Option<String>,
+    #[serde(default = "default_timeout")]
+    pub timeout: u64, // seconds
+    #[serde(default)]
+    pub api_version: Option<String>, // e.g. "1.43"; negotiated with the daemon when unset
+}
+
+/// Resolved client key, client certificate and CA paths of a TLS profile
+#[derive(Debug, Clone)]
+pub struct TlsPaths {
+    pub key: PathBuf,
+    pub cert: PathBuf,
+    pub ca: PathBuf,
+}
+
+impl ConnectionProfile {
+    /// Returns the TLS material to use, or `None` for a plaintext connection
+    pub fn tls_paths(&self) -> Option<TlsPaths> {
+        if self.tls_cert_dir.is_none()
+            && self.tls_key.is_none()
+            && self.tls_cert.is_none()
+            && self.tls_ca.is_none()
+        {
+            return None;
+        }
+
+        let dir = PathBuf::from(self.tls_cert_dir.as_deref().unwrap_or_default());
+        let resolve = |explicit: &Option<String>, file: &str| {
+            explicit
+                .as_ref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| dir.join(file))
+        };
+
+        Some(TlsPaths {
+            key: resolve(&self.tls_key, "key.pem"),
+            cert: resolve(&self.tls_cert, "cert.pem"),
+            ca: resolve(&self.tls_ca, "ca.pem"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionProfiles {
+    pub active: String,
+    pub profiles: Vec<ConnectionProfile>, // the environment default comes first
+}
+
+/// A registry login entered in the app. Its password or access token is kept
+/// in the system keychain, not in the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryCredential {
+    pub registry: String,
+    pub username: String,
+    // Where earlier versions kept the secret; moved to the keychain at startup
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+}
+
+/// On-disk application settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<ConnectionProfile>,
+    #[serde(default)]
+    pub registries: Vec<RegistryCredential>,
+}
+
+fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+pub fn config_path() -> Result<PathBuf> {
+    dirs::config_dir()
+        .map(|dir| dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+        .ok_or_else(|| DockerError::OperationFailed {
+            message: "Could not determine the user configuration directory".to_string(),
+        })
+}
+
+pub fn load_config() -> Result<AppConfig> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+
+    let contents = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Writes the settings file. It names hosts, certificates and registry
+/// logins, so on Unix it is readable by its owner only.
+pub fn save_config(config: &AppConfig) -> Result<()> {
+    let path = config_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies to new files; tighten one written earlier
+        if path.exists() {
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    let mut file = options.open(&path)?;
+    file.write_all(serde_json::to_string_pretty(config)?.as_bytes())?;
+    info!("Saved configuration to {}", path.display());
+    Ok(())
+}
+
+pub fn list_profiles() -> Result<ConnectionProfiles> {
+    let config = load_config()?;
+    let mut profiles = vec![default_profile()];
+    profiles.extend(config.profiles);
+
+    let active = config
+        .active_profile
+        .filter(|name| profiles.iter().any(|p| &p.name == name))
+        .unwrap_or_else(|| DEFAULT_PROFILE_NAME.to_string());
+
+    Ok(ConnectionProfiles { active, profiles })
+}
+
+/// Returns the profile the client should connect with
+pub fn active_profile() -> Result<ConnectionProfile> {
+    let ConnectionProfiles { active, profiles } = list_profiles()?;
+    Ok(profiles
+        .into_iter()
+        .find(|p| p.name == active)
+        .unwrap_or_else(default_profile))
+}
+
+pub fn get_profile(name: &str) -> Result<ConnectionProfile> {
+    list_profiles()?
+        .profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| DockerError::InvalidInput {
+            message: format!("Connection profile '{name}' does not exist"),
+        })
+}
+
+/// Adds a profile, replacing any existing profile with the same name
+pub fn save_profile(profile: ConnectionProfile) -> Result<()> {
+    validate_profile(&profile)?;
+
+    let mut config = load_config()?;
+    match config.profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => *existing = profile,
+        None => config.profiles.push(profile),
+    }
+    save_config(&config)
+}
+
+pub fn delete_profile(name: &str) -> Result<()> {
+    if name == DEFAULT_PROFILE_NAME {
+        return Err(DockerError::InvalidInput {
+            message: "The default connection profile cannot be removed".to_string(),
+        });
+    }
+
+    let mut config = load_config()?;
+    let before = config.profiles.len();
+    config.profiles.retain(|p| p.name != name);
+    if config.profiles.len() == before {
+        return Err(DockerError::InvalidInput {
+            message: format!("Connection profile '{name}' does not exist"),
+        });
+    }
+    if config.active_profile.as_deref() == Some(name) {
+        config.active_profile = None;
+    }
+    save_config(&config)
+}
+
+pub fn set_active_profile(name: &str) -> Result<ConnectionProfile> {
+    let profile = get_profile(name)?;
+
+    let mut config = load_config()?;
+    config.active_profile = (name != DEFAULT_PROFILE_NAME).then(|| name.to_string());
+    save_config(&config)?;
+    Ok(profile)
+}
+
+fn validate_profile(profile: &ConnectionProfile) -> Result<()> {
+    let invalid = |message: String| Err(DockerError::InvalidInput { message });
+
+    if profile.name.trim().is_empty() {
+        return invalid("Connection profile name cannot be empty".to_string());
+    }
+    if profile.name == DEFAULT_PROFILE_NAME {
+        return invalid(format!(
+            "'{DEFAULT_PROFILE_NAME}' is reserved for the environment profile"
+        ));
+    }
+    if let Some(version) = &profile.api_version {
+        parse_api_version(version)?;
+    }
+
+    match &profile.endpoint {
+        ConnectionEndpoint::Unix { socket_path } if socket_path.trim().is_empty() => {
+            invalid("Socket path cannot be empty".to_string())
+        }
+        ConnectionEndpoint::Unix { .. } | ConnectionEndpoint::Ssh { .. }
+            if profile.tls_paths().is_some() =>
+        {
+            invalid("TLS can only be used with TCP connections".to_string())
+        }
+        ConnectionEndpoint::Ssh { destination, .. } if destination.trim().is_empty() => {
+            invalid("SSH destination cannot be empty".to_string())
+        }
+        ConnectionEndpoint::Ssh { destination, .. } if destination.trim().starts_with('-') => {
+            invalid(format!(
+                "SSH destination '{destination}' cannot start with '-'"
+            ))
+        }
+        ConnectionEndpoint::Tcp { url }
+            if !(url.starts_with("tcp://")
+                || url.starts_with("http://")
+                || url.starts_with("https://")) =>
+        {
+            invalid(format!("Unsupported Docker host URL '{url}'"))
+        }
+        ConnectionEndpoint::Tcp { url }
+            if url.starts_with("https://") && profile.tls_paths().is_none() =>
+        {
+            invalid(format!("{url} needs TLS certificates"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parses an API version such as `1.43` (an optional leading `v` is accepted)
+pub fn parse_api_version(version: &str) -> Result<(usize, usize)> {
+    version
+        .trim_start_matches('v')
+        .split_once('.')
+        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        .ok_or_else(|| DockerError::InvalidInput {
+            message: format!("Invalid Docker API version '{version}'"),
+        })
+}
+
+/// Builds the profile implied by the environment: `DOCKER_HOST` wins, then the
+/// endpoint of `DOCKER_CONTEXT` (or the CLI's current context), then the
+/// platform's default socket.
+pub fn default_profile() -> ConnectionProfile {
+    let docker_host = std::env::var("DOCKER_HOST")
+        .ok()
+        .filter(|host| !host.is_empty());
+
+    // An https:// host means TLS, as it does for the docker CLI
+    let tls_verify = std::env::var("DOCKER_TLS_VERIFY")
+        .ok()
+        .is_some_and(|v| !v.is_empty() && v != "0");
+    let https_host = docker_host
+        .as_deref()
+        .is_some_and(|host| host.starts_with("https://"));
+    let tls_cert_dir = (tls_verify || https_host).then(|| {
+        std::env::var("DOCKER_CERT_PATH").unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_default()
+                .join(".docker")
+                .to_string_lossy()
+                .to_string()
+        })
+    });
+
+    let endpoint = docker_host
+        .and_then(|host| endpoint_from_host(&host))
+        .or_else(context_endpoint)
+        .unwrap_or_else(default_socket_endpoint);
+
+    ConnectionProfile {
+        name: DEFAULT_PROFILE_NAME.to_string(),
+        endpoint,
+        tls_cert_dir,
+        tls_key: None,
+        tls_cert: None,
+        tls_ca: None,
+        timeout: DEFAULT_TIMEOUT_SECS,
+        api_version: std::env::var("DOCKER_API_VERSION").ok(),
+    }
+}
+
+fn endpoint_from_host(host: &str) -> Option<ConnectionEndpoint> {
+    if let Some(path) = host.strip_prefix("unix://") {
+        Some(ConnectionEndpoint::Unix {
+            socket_path: path.to_string(),
+        })
+    } else if let Some(path) = host.strip_prefix("npipe://") {
+        Some(ConnectionEndpoint::Unix {
+            socket_path: path.to_string(),
+        })
+    } else if host.starts_with("ssh://") {
+        Some(ConnectionEndpoint::Ssh {
+            destination: host.to_string(),
+            remote_socket: None,
+            identity_file: None,
+        })
+    } else if host.starts_with("tcp://")
+        || host.starts_with("http://")
+        || host.starts_with("https://")
+    {
+        Some(ConnectionEndpoint::Tcp {
+            url: host.to_string(),
+        })
+    } else {
+        warn!("Ignoring unsupported Docker host '{}'", host);
+        None
+    }
+}
+
+/// Resolves the Docker endpoint of the selected CLI context from
+/// `~/.docker/contexts/meta/*/meta.json`.
+fn context_endpoint() -> Option<ConnectionEndpoint> {
+    let docker_dir = dirs::home_dir()?.join(".docker");
+    let context = std::env::var("DOCKER_CONTEXT")
+        .ok()
+        .filter(|c| !c.is_empty())
+        .or_else(|| current_cli_context(&docker_dir))?;
+    if context == "default" {
+        return None;
+    }
+
+    let meta_dir = docker_dir.join("contexts").join("meta");
+    for entry in std::fs::read_dir(meta_dir).ok()?.flatten() {
+        let Ok(contents) = std::fs::read_to_string(entry.path().join("meta.json")) else {
+            continue;
+        };
+        let Ok(meta) = serde_json::from_str::<serde_json::Value>(&contents) else {
+            continue;
+        };
+        if meta["Name"].as_str() == Some(context.as_str()) {
+            return meta["Endpoints"]["docker"]["Host"]
+                .as_str()
+                .and_then(endpoint_from_host);
+        }
+    }
+
+    warn!("Docker context '{}' not found", context);
+    None
+}
+
+fn current_cli_context(docker_dir: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(docker_dir.join("config.json")).ok()?;
+    let config: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    config["currentContext"].as_str().map(str::to_string)
+}
+
+/// The system socket, or the rootless socket under `XDG_RUNTIME_DIR` when
+/// only that one exists.
+fn default_socket_endpoint() -> ConnectionEndpoint {
+    let rootless = std::env::var("XDG_RUNTIME_DIR")
+        .ok()
+        .map(|dir| Path::new(&dir).join("docker.sock"))
+        .filter(|path| path.exists());
+
+    let socket_path = match rootless {
+        Some(path) if !Path::new(DEFAULT_SOCKET_PATH).exists() => {
+            path.to_string_lossy().to_string()
+        }
+        _ => DEFAULT_SOCKET_PATH.to_string(),
+    };
+
+    ConnectionEndpoint::Unix { socket_path }
+}
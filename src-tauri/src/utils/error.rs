@@ -5,6 +5,12 @@ pub enum DockerError {
     #[error("Docker connection failed: {0}")]
     Connection(#[from] bollard::errors::Error),
 
+    #[error("TLS certificate error: {message}")]
+    Certificate { message: String },
+
+    #[error("SSH tunnel error: {message}")]
+    SshTunnel { message: String },
+
     #[error("Docker daemon not available")]
     DaemonNotAvailable,
 
@@ -14,14 +20,15 @@ pub enum DockerError {
     #[error("Image not found: {id}")]
     ImageNotFound { id: String },
 
-    #[allow(dead_code)]
     #[error("Network not found: {id}")]
     NetworkNotFound { id: String },
 
-    #[allow(dead_code)]
     #[error("Volume not found: {id}")]
     VolumeNotFound { id: String },
 
+    #[error("Exec session not found: {id}")]
+    ExecSessionNotFound { id: String },
+
     #[error("Operation failed: {message}")]
     OperationFailed { message: String },
 
@@ -31,7 +38,6 @@ pub enum DockerError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[allow(dead_code)]
     #[error("Invalid input: {message}")]
     InvalidInput { message: String },
 }
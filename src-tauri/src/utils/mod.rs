@@ -1,3 +1,4 @@
+pub mod config;
 pub mod error;
 pub mod logger;
 
@@ -40,6 +41,46 @@ pub fn format_duration(seconds: i64) -> String {
     }
 }
 
+/// Resolves a point in time given the way the daemon accepts `since` and
+/// `until`: a duration before now (`90m`, `24h`, `1h30m`), a unix timestamp
+/// or an RFC 3339 date. Returns seconds since the epoch.
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    if let Ok(timestamp) = value.parse::<f64>() {
+        return Some(timestamp as i64);
+    }
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(date.timestamp());
+    }
+
+    let mut seconds = 0.0;
+    let mut number = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let amount: f64 = number.parse().ok()?;
+        number.clear();
+        seconds += amount
+            * match c {
+                'h' => 3600.0,
+                'm' if chars.peek() == Some(&'s') => {
+                    chars.next();
+                    0.001
+                }
+                'm' => 60.0,
+                's' => 1.0,
+                _ => return None,
+            };
+    }
+    if !number.is_empty() || seconds == 0.0 {
+        return None;
+    }
+
+    Some(chrono::Utc::now().timestamp() - seconds as i64)
+}
+
 #[allow(dead_code)]
 pub fn parse_image_tag(image: &str) -> (String, String) {
     if let Some(pos) = image.rfind(':') {
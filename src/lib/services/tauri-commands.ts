@@ -1,76 +1,169 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { 
+  ConnectionProfile,
+  ConnectionProfiles,
+  DiskUsage,
   DockerConnectionStatus, 
   DockerSystemInfo, 
   DockerStats 
 } from '../types/docker';
 import type { 
   Container, 
+  ContainerAction,
+  ContainerActionResult,
+  ContainerPatch,
+  ContainerDefinition,
+  ContainerResources,
+  ContainerUpdateResult,
+  RestartPolicySpec,
   ContainerInspect, 
   ContainerStats,
+  ContainerSpec,
   ContainerLogLine 
 } from '../types/container';
 import type { 
   ImageSummary, 
-  ImageInspect 
+  ImageInspect,
+  ImageBuildRequest,
+  ImageHistoryEntry,
+  ImagePruneResponse,
+  ImageRetagResult,
+  RegistryLogin
 } from '../types/image';
 
 // System API
-export async function connectDocker(): Promise<boolean> {
-  return await invoke('connect_docker');
+export async function connectDocker(host?: string): Promise<boolean> {
+  return await invoke('connect_docker', { host });
 }
 
-export async function disconnectDocker(): Promise<void> {
-  return await invoke('disconnect_docker');
+export async function disconnectDocker(host?: string): Promise<void> {
+  return await invoke('disconnect_docker', { host });
 }
 
-export async function getDockerConnectionStatus(): Promise<DockerConnectionStatus> {
-  return await invoke('get_docker_connection_status');
+export async function getConnectedHosts(): Promise<string[]> {
+  return await invoke('get_connected_hosts');
 }
 
-export async function getSystemInfo(): Promise<DockerSystemInfo> {
-  return await invoke('get_system_info');
+export async function getDockerConnectionStatus(host?: string): Promise<DockerConnectionStatus> {
+  return await invoke('get_docker_connection_status', { host });
 }
 
-export async function getSystemStats(): Promise<DockerStats> {
-  return await invoke('get_system_stats');
+export async function getSystemInfo(host?: string): Promise<DockerSystemInfo> {
+  return await invoke('get_system_info', { host });
 }
 
-export async function testDockerConnection(): Promise<boolean> {
-  return await invoke('test_docker_connection');
+export async function getSystemStats(host?: string): Promise<DockerStats> {
+  return await invoke('get_system_stats', { host });
+}
+
+export async function getDiskUsage(host?: string): Promise<DiskUsage> {
+  return await invoke('get_disk_usage', { host });
+}
+
+export async function testDockerConnection(host?: string): Promise<boolean> {
+  return await invoke('test_docker_connection', { host });
+}
+
+export async function getConnectionProfiles(): Promise<ConnectionProfiles> {
+  return await invoke('get_connection_profiles');
+}
+
+export async function addConnectionProfile(profile: ConnectionProfile): Promise<void> {
+  return await invoke('add_connection_profile', { profile });
+}
+
+export async function removeConnectionProfile(name: string): Promise<void> {
+  return await invoke('remove_connection_profile', { name });
+}
+
+export async function switchConnectionProfile(name: string): Promise<boolean> {
+  return await invoke('switch_connection_profile', { name });
 }
 
 // Container API
-export async function getContainers(all: boolean = true): Promise<Container[]> {
-  return await invoke('get_containers', { all });
+export async function getContainers(all: boolean = true, host?: string): Promise<Container[]> {
+  return await invoke('get_containers', { all, host });
+}
+
+export async function getContainerDetails(id: string, host?: string): Promise<ContainerInspect> {
+  return await invoke('get_container_details', { id, host });
+}
+
+export async function createContainer(
+  spec: ContainerSpec,
+  pull: boolean = false,
+  start: boolean = true,
+  host?: string
+): Promise<ContainerInspect> {
+  return await invoke('create_container_cmd', { spec, pull, start, host });
 }
 
-export async function getContainerDetails(id: string): Promise<ContainerInspect> {
-  return await invoke('get_container_details', { id });
+export async function startContainer(id: string, host?: string): Promise<void> {
+  return await invoke('start_container_cmd', { id, host });
 }
 
-export async function startContainer(id: string): Promise<void> {
-  return await invoke('start_container_cmd', { id });
+export async function stopContainer(id: string, timeout?: number, host?: string): Promise<void> {
+  return await invoke('stop_container_cmd', { id, timeout, host });
 }
 
-export async function stopContainer(id: string, timeout?: number): Promise<void> {
-  return await invoke('stop_container_cmd', { id, timeout });
+export async function restartContainer(id: string, timeout?: number, host?: string): Promise<void> {
+  return await invoke('restart_container_cmd', { id, timeout, host });
 }
 
-export async function restartContainer(id: string, timeout?: number): Promise<void> {
-  return await invoke('restart_container_cmd', { id, timeout });
+export async function removeContainer(id: string, force: boolean = false, removeVolumes: boolean = false, host?: string): Promise<void> {
+  return await invoke('remove_container_cmd', { id, force, removeVolumes, host });
 }
 
-export async function removeContainer(id: string, force: boolean = false, removeVolumes: boolean = false): Promise<void> {
-  return await invoke('remove_container_cmd', { id, force, removeVolumes });
+export async function killContainer(id: string, signal?: string, host?: string): Promise<void> {
+  return await invoke('kill_container_cmd', { id, signal, host });
 }
 
-export async function killContainer(id: string, signal?: string): Promise<void> {
-  return await invoke('kill_container_cmd', { id, signal });
+export async function pauseContainer(id: string, host?: string): Promise<void> {
+  return await invoke('pause_container_cmd', { id, host });
 }
 
-export async function getContainerStats(id: string): Promise<ContainerStats> {
-  return await invoke('get_container_stats_cmd', { id });
+export async function unpauseContainer(id: string, host?: string): Promise<void> {
+  return await invoke('unpause_container_cmd', { id, host });
+}
+
+// Unset limits are left unchanged
+export async function updateContainerResources(
+  id: string,
+  resources: ContainerResources,
+  restartPolicy?: RestartPolicySpec,
+  host?: string
+): Promise<ContainerUpdateResult> {
+  return await invoke('update_container_resources_cmd', { id, resources, restartPolicy, host });
+}
+
+// Replaces the container, keeping its name, networks and mounts; the
+// original is restored if the new one fails to start
+export async function recreateContainer(id: string, patch: ContainerPatch, host?: string): Promise<ContainerInspect> {
+  return await invoke('recreate_container_cmd', { id, patch, host });
+}
+
+export async function exportContainerDefinition(id: string, host?: string): Promise<ContainerDefinition> {
+  return await invoke('export_container_definition_cmd', { id, host });
+}
+
+export async function bulkContainerAction(
+  ids: string[],
+  action: ContainerAction['action'],
+  host?: string
+): Promise<ContainerActionResult[]> {
+  return await invoke('bulk_container_action_cmd', { ids, action, host });
+}
+
+export async function getContainerStats(id: string, host?: string): Promise<ContainerStats> {
+  return await invoke('get_container_stats_cmd', { id, host });
+}
+
+export async function subscribeContainerStats(id: string, host?: string): Promise<string> {
+  return await invoke('subscribe_container_stats_cmd', { id, host });
+}
+
+export async function unsubscribeContainerStats(subscriptionId: string): Promise<void> {
+  return await invoke('unsubscribe_container_stats_cmd', { subscriptionId });
 }
 
 export async function getContainerLogs(
@@ -78,32 +171,146 @@ export async function getContainerLogs(
   follow?: boolean,
   tail?: string,
   since?: string,
-  until?: string
+  until?: string,
+  host?: string
 ): Promise<ContainerLogLine[]> {
   return await invoke('get_container_logs_cmd', { 
     id, 
     follow, 
     tail, 
     since, 
-    until 
+    until,
+    host
   });
 }
 
+export async function subscribeContainerLogs(
+  id: string,
+  tail?: string,
+  since?: string,
+  host?: string
+): Promise<string> {
+  return await invoke('subscribe_container_logs_cmd', { id, tail, since, host });
+}
+
+export async function unsubscribeContainerLogs(subscriptionId: string): Promise<void> {
+  return await invoke('unsubscribe_container_logs_cmd', { subscriptionId });
+}
+
+// Terminal API
+export async function openExecSession(id: string, cmd: string[], tty: boolean = true, host?: string): Promise<string> {
+  return await invoke('open_exec_session_cmd', { id, cmd, tty, host });
+}
+
+export async function writeExecStdin(sessionId: string, data: string): Promise<void> {
+  return await invoke('write_exec_stdin_cmd', { sessionId, data });
+}
+
+export async function resizeExecSession(sessionId: string, rows: number, cols: number): Promise<void> {
+  return await invoke('resize_exec_session_cmd', { sessionId, rows, cols });
+}
+
+export async function closeExecSession(sessionId: string): Promise<void> {
+  return await invoke('close_exec_session_cmd', { sessionId });
+}
+
 // Image API
-export async function getImages(all: boolean = false): Promise<ImageSummary[]> {
-  return await invoke('get_images', { all });
+export async function getImages(all: boolean = false, host?: string): Promise<ImageSummary[]> {
+  return await invoke('get_images', { all, host });
+}
+
+export async function getImageDetails(id: string, host?: string): Promise<ImageInspect> {
+  return await invoke('get_image_details', { id, host });
+}
+
+export async function getImageHistory(id: string, host?: string): Promise<ImageHistoryEntry[]> {
+  return await invoke('get_image_history', { id, host });
+}
+
+export async function removeImage(id: string, force: boolean = false, noPrune: boolean = false, host?: string): Promise<void> {
+  return await invoke('remove_image_cmd', { id, force, noPrune, host });
+}
+
+export async function tagImage(id: string, reference: string, host?: string): Promise<void> {
+  return await invoke('tag_image_cmd', { id, reference, host });
+}
+
+// Removes one tag; fails instead of deleting the image when it is the last one
+export async function untagImage(reference: string, host?: string): Promise<void> {
+  return await invoke('untag_image_cmd', { reference, host });
+}
+
+export async function retagImages(sources: string[], prefix: string, host?: string): Promise<ImageRetagResult[]> {
+  return await invoke('retag_images_cmd', { sources, prefix, host });
+}
+
+// `until` takes a duration (`24h`) or timestamp; `labels` are `key` or `key=value`
+export async function pruneImages(
+  all: boolean = false,
+  until?: string,
+  labels?: string[],
+  dryRun: boolean = false,
+  host?: string
+): Promise<ImagePruneResponse> {
+  return await invoke('prune_images_cmd', { all, until, labels, dryRun, host });
+}
+
+export async function pullImage(name: string, tag?: string, host?: string): Promise<void> {
+  return await invoke('pull_image_cmd', { name, tag, host });
+}
+
+// Progress arrives as `image-pull-progress:{id}` events (ImagePullProgress),
+// completion as `image-pull-end:{id}` carrying an error message or null
+export async function subscribeImagePull(name: string, tag?: string, host?: string): Promise<string> {
+  return await invoke('subscribe_image_pull_cmd', { name, tag, host });
+}
+
+export async function cancelImagePull(subscriptionId: string): Promise<void> {
+  return await invoke('cancel_image_pull_cmd', { subscriptionId });
+}
+
+// Log lines arrive as `image-build-progress:{id}` events (ImageBuildProgress),
+// completion as `image-build-end:{id}` carrying an ImageBuildEnd
+export async function buildImage(request: ImageBuildRequest, host?: string): Promise<string> {
+  return await invoke('build_image_cmd', { request, host });
+}
+
+export async function cancelImageBuild(subscriptionId: string): Promise<void> {
+  return await invoke('cancel_image_build_cmd', { subscriptionId });
+}
+
+// Progress arrives as `image-save-progress:{id}` events (ImageArchiveProgress),
+// completion as `image-save-end:{id}` carrying an error message or null
+export async function saveImages(images: string[], path: string, host?: string): Promise<string> {
+  return await invoke('save_images_cmd', { images, path, host });
+}
+
+// Progress arrives as `image-load-progress:{id}` events (ImageArchiveProgress),
+// completion as `image-load-end:{id}` carrying an ImageLoadEnd
+export async function loadImages(path: string, host?: string): Promise<string> {
+  return await invoke('load_images_cmd', { path, host });
+}
+
+export async function cancelImageArchive(subscriptionId: string): Promise<void> {
+  return await invoke('cancel_image_archive_cmd', { subscriptionId });
+}
+
+// Progress arrives as `image-push-progress:{id}` events (ImagePushProgress),
+// completion as `image-push-end:{id}` carrying an error message or null
+export async function pushImage(name: string, tag?: string, host?: string): Promise<string> {
+  return await invoke('push_image_cmd', { name, tag, host });
 }
 
-export async function getImageDetails(id: string): Promise<ImageInspect> {
-  return await invoke('get_image_details', { id });
+export async function getRegistryLogins(): Promise<RegistryLogin[]> {
+  return await invoke('get_registry_logins');
 }
 
-export async function removeImage(id: string, force: boolean = false, noPrune: boolean = false): Promise<void> {
-  return await invoke('remove_image_cmd', { id, force, noPrune });
+export async function addRegistryLogin(registry: string, username: string, password: string): Promise<void> {
+  return await invoke('add_registry_login', { registry, username, password });
 }
 
-export async function pullImage(name: string, tag?: string): Promise<void> {
-  return await invoke('pull_image_cmd', { name, tag });
+export async function removeRegistryLogin(registry: string): Promise<void> {
+  return await invoke('remove_registry_login', { registry });
 }
 
 // Helper function to handle API errors
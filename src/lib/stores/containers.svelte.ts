@@ -4,15 +4,19 @@ import {
 	getContainerStats,
 	getContainers,
 	handleTauriError,
+	bulkContainerAction,
 	killContainer,
+	pauseContainer,
 	removeContainer,
 	restartContainer,
 	startContainer,
 	stopContainer,
+	unpauseContainer,
 } from "../services/tauri-commands";
 // src/lib/stores/containers.svelte.ts
 import type {
 	Container,
+	ContainerAction,
 	ContainerInspect,
 	ContainerLogLine,
 	ContainerStats,
@@ -226,7 +230,7 @@ class ContainersStore {
 	}
 
 	async performContainerAction(
-		action: "start" | "stop" | "restart" | "remove" | "kill",
+		action: ContainerAction["action"],
 		id: string,
 		options?: {
 			timeout?: number;
@@ -259,6 +263,12 @@ class ContainersStore {
 				case "kill":
 					await killContainer(id, options?.signal);
 					break;
+				case "pause":
+					await pauseContainer(id);
+					break;
+				case "unpause":
+					await unpauseContainer(id);
+					break;
 				default:
 					throw new Error(`Unknown action: ${action}`);
 			}
@@ -297,6 +307,42 @@ class ContainersStore {
 		return this.performContainerAction("kill", id, { signal });
 	}
 
+	async pauseContainer(id: string) {
+		return this.performContainerAction("pause", id);
+	}
+
+	async unpauseContainer(id: string) {
+		return this.performContainerAction("unpause", id);
+	}
+
+	// Runs one action on several containers; returns the ids that failed
+	async performBulkContainerAction(
+		action: ContainerAction["action"],
+		ids: string[],
+	) {
+		try {
+			this._containerOperationInProgress = `${action}-bulk`;
+			this._containerError = null;
+
+			const results = await bulkContainerAction(ids, action);
+			const failed = results.filter((result) => result.error);
+			if (failed.length > 0) {
+				this._containerError = failed
+					.map((result) => `${result.id.slice(0, 12)}: ${result.error}`)
+					.join("\n");
+			}
+
+			await this.loadContainers();
+			return failed.map((result) => result.id);
+		} catch (error) {
+			console.error(`Failed to ${action} containers:`, error);
+			this._containerError = handleTauriError(error);
+			return ids;
+		} finally {
+			this._containerOperationInProgress = null;
+		}
+	}
+
 	// Auto-refresh functionality
 	startAutoRefresh(intervalMs = 3000) {
 		if (this.containerRefreshInterval) {
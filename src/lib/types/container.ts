@@ -457,6 +457,57 @@ export interface ContainerCreateOptions {
 	};
 }
 
+export interface PortMapping {
+	container_port: number;
+	protocol?: "tcp" | "udp" | "sctp";
+	host_ip?: string;
+	host_port?: number; // unset publishes on a random port
+}
+
+export interface MountSpec {
+	type: "bind" | "volume" | "tmpfs";
+	source?: string; // host path or volume name
+	target: string;
+	read_only?: boolean;
+}
+
+export interface NetworkAttachment {
+	name: string;
+	aliases?: string[];
+	ipv4_address?: string;
+}
+
+export interface RestartPolicySpec {
+	name: "no" | "always" | "unless-stopped" | "on-failure";
+	maximum_retry_count?: number;
+}
+
+export interface ContainerResources {
+	memory?: number; // bytes
+	memory_swap?: number; // memory plus swap in bytes, -1 for unlimited
+	cpu_shares?: number;
+	nano_cpus?: number;
+	cpuset_cpus?: string;
+	pids_limit?: number;
+}
+
+// Typed create request; unset fields keep the image's or daemon's defaults
+export interface ContainerSpec {
+	image: string;
+	name?: string;
+	command?: string[];
+	entrypoint?: string[];
+	env?: string[]; // KEY=value
+	ports?: PortMapping[];
+	mounts?: MountSpec[];
+	networks?: NetworkAttachment[];
+	restart_policy?: RestartPolicySpec;
+	labels?: Record<string, string>;
+	resources?: ContainerResources;
+	user?: string;
+	working_dir?: string;
+}
+
 export interface ContainerAction {
 	id: string;
 	action:
@@ -472,6 +523,86 @@ export interface ContainerAction {
 	force?: boolean;
 }
 
+// Unset fields keep the container's current value; lists and maps replace it
+export interface ContainerPatch {
+	image?: string;
+	command?: string[];
+	entrypoint?: string[];
+	env?: string[];
+	ports?: PortMapping[];
+	labels?: Record<string, string>;
+	restart_policy?: RestartPolicySpec;
+	resources?: ContainerResources;
+	user?: string;
+	working_dir?: string;
+}
+
+export interface ContainerUpdateResult {
+	warnings: string[];
+	host_config: ContainerHostConfig; // as inspected after the update
+}
+
+export interface ContainerDefinition {
+	spec: ContainerSpec; // image defaults left out
+	options: ContainerRuntimeOptions;
+	unexported: string[]; // `docker run` flags in use that neither output reproduces
+	run_command: string; // `docker run ...`, plus `docker network connect` lines
+	compose: string; // compose YAML with the container as its only service
+}
+
+export interface ContainerRuntimeOptions {
+	hostname?: string;
+	domainname?: string;
+	privileged: boolean;
+	auto_remove: boolean;
+	read_only: boolean;
+	init: boolean;
+	tty: boolean;
+	interactive: boolean;
+	cap_add: string[];
+	cap_drop: string[];
+	devices: string[]; // host_path[:container_path[:permissions]]
+	extra_hosts: string[]; // host:ip
+	dns: string[];
+	dns_search: string[];
+	dns_options: string[];
+	security_opt: string[];
+	group_add: string[];
+	sysctls: Record<string, string>;
+	ulimits: UlimitSpec[];
+	tmpfs: Record<string, string>; // target to mount options
+	shm_size?: number; // bytes
+	ipc_mode?: string;
+	pid_mode?: string;
+	log_driver?: string;
+	log_options: Record<string, string>;
+	healthcheck?: HealthcheckSpec;
+	stop_signal?: string;
+	stop_timeout?: number; // seconds
+	runtime?: string;
+	memory_reservation?: number; // bytes
+}
+
+export interface UlimitSpec {
+	name: string;
+	soft: number;
+	hard: number;
+}
+
+export interface HealthcheckSpec {
+	test: string[]; // ["CMD", ...], ["CMD-SHELL", cmd] or ["NONE"]
+	interval?: number; // nanoseconds, as are the other durations
+	timeout?: number;
+	retries?: number;
+	start_period?: number;
+	start_interval?: number;
+}
+
+export interface ContainerActionResult {
+	id: string;
+	error?: string;
+}
+
 export interface ContainerLogOptions {
 	follow?: boolean;
 	stdout?: boolean;
@@ -484,7 +615,7 @@ export interface ContainerLogOptions {
 
 export interface ContainerLogLine {
 	timestamp?: string;
-	stream: "stdout" | "stderr";
+	stream: "stdout" | "stderr" | "stdin" | "console";
 	content: string;
 }
 
@@ -80,15 +80,12 @@ export interface DockerInfo {
 	init_binary: string;
 	containerd_commit: {
 		id: string;
-		expected: string;
 	};
 	runc_commit: {
 		id: string;
-		expected: string;
 	};
 	init_commit: {
 		id: string;
-		expected: string;
 	};
 	security_options: string[];
 }
@@ -103,6 +100,45 @@ export interface DockerConnectionStatus {
 	error?: string;
 	version?: string;
 	api_version?: string;
+	profile?: string;
+	tunnel?: SshTunnelStatus;
+}
+
+export interface ConnectionHealthEvent {
+	host: string;
+	state: "connected" | "disconnected" | "reconnecting";
+	attempt: number;
+	retry_in_ms?: number;
+	error?: string;
+}
+
+export interface SshTunnelStatus {
+	state: "up" | "reconnecting";
+	destination: string;
+	local_port: number;
+	reconnects: number;
+	last_error?: string;
+}
+
+export type ConnectionEndpoint =
+	| { type: "unix"; socket_path: string }
+	| { type: "tcp"; url: string }
+	| { type: "ssh"; destination: string; remote_socket?: string; identity_file?: string };
+
+export interface ConnectionProfile {
+	name: string;
+	endpoint: ConnectionEndpoint;
+	tls_cert_dir?: string;
+	tls_key?: string;
+	tls_cert?: string;
+	tls_ca?: string;
+	timeout: number;
+	api_version?: string;
+}
+
+export interface ConnectionProfiles {
+	active: string;
+	profiles: ConnectionProfile[];
 }
 
 export interface DockerStats {
@@ -115,7 +151,32 @@ export interface DockerStats {
 	networks_total: number;
 }
 
+export interface DiskUsageCategory {
+	total_count: number;
+	active_count: number;
+	size: number;
+	reclaimable: number;
+}
+
+export interface DiskUsageItem {
+	category: 'image' | 'container' | 'volume' | 'build-cache';
+	id: string;
+	name: string;
+	size: number;
+}
+
+export interface DiskUsage {
+	images: DiskUsageCategory;
+	containers: DiskUsageCategory;
+	volumes: DiskUsageCategory;
+	build_cache: DiskUsageCategory;
+	total_size: number;
+	reclaimable: number;
+	top_consumers: DiskUsageItem[]; // largest first
+}
+
 export interface DockerEvent {
+	host: string;
 	type: string;
 	action: string;
 	actor: {
@@ -20,40 +20,6 @@ export interface ImageInspect {
 	parent: string;
 	comment: string;
 	created: string;
-	container: string;
-	container_config: {
-		hostname: string;
-		domainname: string;
-		user: string;
-		attach_stdin: boolean;
-		attach_stdout: boolean;
-		attach_stderr: boolean;
-		exposed_ports?: Record<string, Record<string, unknown>>;
-		tty: boolean;
-		open_stdin: boolean;
-		stdin_once: boolean;
-		env: string[];
-		cmd: string[];
-		healthcheck?: {
-			test: string[];
-			interval: number;
-			timeout: number;
-			retries: number;
-			start_period: number;
-		};
-		args_escaped: boolean;
-		image: string;
-		volumes?: Record<string, Record<string, unknown>>;
-		working_dir: string;
-		entrypoint: string[] | null;
-		network_disabled: boolean;
-		mac_address: string;
-		on_build: string[] | null;
-		labels: Record<string, string>;
-		stop_signal: string;
-		stop_timeout: number;
-		shell: string[];
-	};
 	docker_version: string;
 	author: string;
 	config: {
@@ -113,6 +79,7 @@ export interface ImageHistoryEntry {
 	tags: string[];
 	size: number;
 	comment: string;
+	cumulative_size: number;
 }
 
 export interface ImageBuildOptions {
@@ -204,6 +171,75 @@ export interface BuildProgress {
 	};
 }
 
+export interface ImagePullProgress {
+	layer_id?: string;
+	status: string;
+	progress?: string;
+	current?: number;
+	total?: number;
+	layers_total: number;
+	layers_done: number;
+	percent: number;
+}
+
+export interface ImagePushProgress {
+	status: string;
+	progress?: string;
+	current?: number;
+	total?: number;
+}
+
+export interface ImageBuildRequest {
+	context_dir: string;
+	dockerfile?: string; // relative to the context, `Dockerfile` by default
+	tags?: string[];
+	build_args?: Record<string, string>;
+	target?: string;
+	platform?: string;
+	no_cache?: boolean;
+	pull?: boolean;
+}
+
+export interface ImageBuildProgress {
+	stream?: string;
+	status?: string;
+	progress?: string;
+}
+
+export interface ImageBuildEnd {
+	image_id?: string;
+	error?: string;
+}
+
+export interface ImageArchiveProgress {
+	bytes: number;
+	total_bytes: number; // for saves, estimated from the image sizes
+	percent: number;
+}
+
+export interface ImageLoadEnd {
+	loaded: string[];
+	error?: string;
+}
+
+export interface ImagePruneResponse {
+	images_deleted?: string[];
+	space_reclaimed?: number;
+	dry_run: boolean; // nothing was removed; the fields list what would be
+}
+
+export interface ImageRetagResult {
+	source: string;
+	target: string;
+	error?: string;
+}
+
+export interface RegistryLogin {
+	registry: string;
+	username?: string;
+	source: "app" | "docker-config" | "credential-helper";
+}
+
 export type ImageStatus =
 	| "available"
 	| "pulling"